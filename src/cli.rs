@@ -0,0 +1,263 @@
+use crate::diagnostics::Diagnostic;
+use crate::dot;
+use crate::evaluator::eval;
+use crate::formatter::format;
+use crate::interrupt;
+use crate::json;
+use crate::lint::{lint, Config};
+use crate::object::{Environment, ObjectType};
+use crate::optimizer::optimize;
+use crate::parser::parse;
+use crate::repl;
+use crate::resolver::resolve;
+use crate::utils::color_enabled;
+use std::fs;
+use std::io::stderr;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: lolo [file]
+       lolo check <file>...
+       lolo lint <file>...
+       lolo fmt [--check] <file>...
+       lolo ast (--json | --dot) <file>
+       lolo callgraph --dot <file>
+
+with no file, starts the repl
+
+commands:
+    check    report undefined names and other mistakes without running the files
+    lint     warn about code that is probably wrong, configured by the nearest lolo.toml
+    fmt      rewrite the files in the standard style, or with --check only
+             report the files that would change
+    ast      print the tokens and syntax tree of a file as json, in the
+             schema described in src/json.rs, or the tree as a graphviz graph
+    callgraph
+             print which named functions call which as a graphviz graph";
+
+// exit status of a script stopped with Ctrl-C, as shells report it
+const INTERRUPTED: u8 = 130;
+
+pub fn run(args: &[String]) -> ExitCode {
+    match args.first().map(|x| x.as_str()) {
+        None => run_repl(),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Some("check") if args.len() > 1 => check(&args[1..]),
+        Some("lint") if args.len() > 1 => run_lint(&args[1..]),
+        Some("fmt") if args.len() > 1 => run_fmt(&args[1..]),
+        Some("ast") if args.len() > 1 => run_ast(&args[1..]),
+        Some("callgraph") if args.len() > 1 => run_call_graph(&args[1..]),
+        Some("check") | Some("lint") | Some("fmt") | Some("ast") | Some("callgraph") => {
+            usage_error()
+        }
+        Some(path) => run_file(path),
+    }
+}
+
+fn check(paths: &[String]) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+
+    for path in paths {
+        let source = match read_source(path) {
+            Some(x) => x,
+            None => {
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let diagnostics = match parse(&source) {
+            Ok(program) => resolve(&program),
+            Err(errors) => errors,
+        };
+
+        print_diagnostics(&diagnostics, &source, path);
+        if diagnostics.iter().any(|x| x.is_error()) {
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    status
+}
+
+// warnings alone do not fail, lints set to deny do
+fn run_lint(paths: &[String]) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+
+    for path in paths {
+        let (source, config) = match (read_source(path), load_lint_config(path)) {
+            (Some(source), Some(config)) => (source, config),
+            _ => {
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let diagnostics = match parse(&source) {
+            Ok(program) => lint(&program, &source, &config),
+            Err(errors) => errors,
+        };
+
+        print_diagnostics(&diagnostics, &source, path);
+        if diagnostics.iter().any(|x| x.is_error()) {
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    status
+}
+
+fn run_fmt(args: &[String]) -> ExitCode {
+    let check = args.iter().any(|x| x == "--check");
+    let mut status = ExitCode::SUCCESS;
+
+    for path in args.iter().filter(|x| *x != "--check") {
+        let source = match read_source(path) {
+            Some(x) => x,
+            None => {
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let formatted = match format(&source) {
+            Ok(x) => x,
+            Err(errors) => {
+                print_diagnostics(&errors, &source, path);
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            eprintln!("lolo: {} is not formatted", path);
+            status = ExitCode::FAILURE;
+        } else if let Err(e) = fs::write(path, formatted) {
+            eprintln!("lolo: {}: {}", path, e);
+            status = ExitCode::FAILURE;
+        }
+    }
+
+    status
+}
+
+fn run_ast(args: &[String]) -> ExitCode {
+    match args {
+        [flag, path] if flag == "--json" => export(path, json::export),
+        [flag, path] if flag == "--dot" => export(path, |x| parse(x).map(|x| dot::ast(&x))),
+        _ => usage_error(),
+    }
+}
+
+fn run_call_graph(args: &[String]) -> ExitCode {
+    match args {
+        [flag, path] if flag == "--dot" => export(path, |x| parse(x).map(|x| dot::call_graph(&x))),
+        _ => usage_error(),
+    }
+}
+
+// prints what a file exports to, or why it could not be parsed
+fn export(path: &str, export: impl Fn(&str) -> Result<String, Vec<Diagnostic>>) -> ExitCode {
+    let source = match read_source(path) {
+        Some(x) => x,
+        None => return ExitCode::FAILURE,
+    };
+
+    match export(&source) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(errors) => {
+            print_diagnostics(&errors, &source, path);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error() -> ExitCode {
+    eprintln!("{}", USAGE);
+    ExitCode::FAILURE
+}
+
+fn load_lint_config(path: &str) -> Option<Config> {
+    let config_path = match Config::find(Path::new(path)) {
+        Some(x) => x,
+        None => return Some(Config::default()),
+    };
+
+    let result = read_source(&config_path.to_string_lossy()).map(|x| Config::parse(&x));
+    match result {
+        Some(Ok(config)) => Some(config),
+        Some(Err(e)) => {
+            eprintln!("lolo: {}: {}", config_path.display(), e);
+            None
+        }
+        None => None,
+    }
+}
+
+fn run_repl() -> ExitCode {
+    match repl::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("lolo: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_file(path: &str) -> ExitCode {
+    let source = match read_source(path) {
+        Some(x) => x,
+        None => return ExitCode::FAILURE,
+    };
+
+    let program = match parse(&source) {
+        Ok(x) => x,
+        Err(errors) => {
+            print_diagnostics(&errors, &source, path);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    interrupt::install_handler();
+    match eval(&optimize(program), &Environment::new()) {
+        Ok(evaluated) => {
+            if evaluated.object_type != ObjectType::Null {
+                println!("{}", evaluated.inspect());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) if e.is_interrupted() => ExitCode::from(INTERRUPTED),
+        Err(e) => {
+            eprint!("{}", e.render(&source, path, color_enabled(&stderr())));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_source(path: &str) -> Option<String> {
+    match fs::read_to_string(path) {
+        Ok(x) => Some(x),
+        Err(e) => {
+            eprintln!("lolo: {}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn print_diagnostics(diagnostics: &[Diagnostic], source: &str, file_name: &str) {
+    let color = color_enabled(&stderr());
+
+    for diagnostic in diagnostics {
+        eprint!("{}", diagnostic.render(source, file_name, color));
+    }
+}
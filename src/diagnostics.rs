@@ -0,0 +1,228 @@
+use crate::lexer::token::Span;
+use std::fmt;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    pub primary: Option<Label>,
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub helps: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error<T: ToString>(code: &str, message: T) -> Diagnostic {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning<T: ToString>(code: &str, message: T) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    fn new<T: ToString>(severity: Severity, code: &str, message: T) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: code.to_string(),
+            message: message.to_string(),
+            primary: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            helps: Vec::new(),
+        }
+    }
+
+    // the span the diagnostic is about, underlined with ^
+    pub fn with_primary<T: ToString>(mut self, span: Span, message: T) -> Diagnostic {
+        self.primary = Some(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    // related spans, underlined with -
+    pub fn with_secondary<T: ToString>(mut self, span: Span, message: T) -> Diagnostic {
+        self.secondary.push(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn with_note<T: ToString>(mut self, note: T) -> Diagnostic {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    pub fn with_help<T: ToString>(mut self, help: T) -> Diagnostic {
+        self.helps.push(help.to_string());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    // renders with the offending source lines, plain or with ANSI colours
+    pub fn render(&self, source: &str, file_name: &str, color: bool) -> String {
+        Renderer::new(source, file_name, color).render(self)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+struct Renderer<'a> {
+    lines: Vec<&'a str>,
+    file_name: &'a str,
+    color: bool,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(source: &'a str, file_name: &'a str, color: bool) -> Renderer<'a> {
+        Renderer {
+            lines: source.lines().collect(),
+            file_name,
+            color,
+        }
+    }
+
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn severity_style(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        }
+    }
+
+    fn render(&self, diagnostic: &Diagnostic) -> String {
+        let style = self.severity_style(diagnostic.severity);
+        let mut output = format!(
+            "{}{}\n",
+            self.paint(
+                &format!("{}[{}]", diagnostic.severity, diagnostic.code),
+                style
+            ),
+            self.paint(&format!(": {}", diagnostic.message), BOLD),
+        );
+
+        let labels: Vec<(&Label, char, &str)> = diagnostic
+            .primary
+            .iter()
+            .map(|label| (label, '^', style))
+            .chain(diagnostic.secondary.iter().map(|label| (label, '-', BLUE)))
+            // a default span points nowhere in the source
+            .filter(|(label, _, _)| label.span != Span::default())
+            .collect();
+
+        let gutter_width = labels
+            .iter()
+            .map(|(label, _, _)| label.span.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+
+        if let Some((first, _, _)) = labels.first() {
+            output.push_str(&format!(
+                "{}{} {}:{}:{}\n",
+                gutter,
+                self.paint("-->", BLUE),
+                self.file_name,
+                first.span.line,
+                first.span.column
+            ));
+        }
+
+        for (label, marker, style) in &labels {
+            let line = match self.lines.get(label.span.line.wrapping_sub(1)) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let line_length = line.chars().count();
+            let start = label.span.column.saturating_sub(1).min(line_length);
+            let width = (label.span.end - label.span.start)
+                .min(line_length - start)
+                .max(1);
+            let underline = marker.to_string().repeat(width);
+
+            output.push_str(&format!("{} {}\n", gutter, self.paint("|", BLUE)));
+            output.push_str(&format!(
+                "{} {} {}\n",
+                self.paint(
+                    &format!("{:>width$}", label.span.line, width = gutter_width),
+                    BLUE
+                ),
+                self.paint("|", BLUE),
+                line
+            ));
+
+            let mut underline_line = format!(
+                "{} {} {}{}",
+                gutter,
+                self.paint("|", BLUE),
+                " ".repeat(start),
+                self.paint(&underline, style)
+            );
+            if !label.message.is_empty() {
+                underline_line.push(' ');
+                underline_line.push_str(&self.paint(&label.message, style));
+            }
+            output.push_str(&underline_line);
+            output.push('\n');
+        }
+
+        let notes = diagnostic.notes.iter().map(|x| ("note", x));
+        let helps = diagnostic.helps.iter().map(|x| ("help", x));
+
+        for (kind, text) in notes.chain(helps) {
+            output.push_str(&format!(
+                "{} {} {}\n",
+                gutter,
+                self.paint("=", BLUE),
+                self.paint(&format!("{}: {}", kind, text), BOLD)
+            ));
+        }
+
+        output
+    }
+}
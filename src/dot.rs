@@ -0,0 +1,286 @@
+use crate::parser::ast::expressions::*;
+use crate::parser::ast::statements::*;
+use crate::parser::ast::visit::*;
+use crate::parser::ast::Program;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// graphviz renderings of a program, to be piped into `dot -Tsvg` and the like
+
+// the syntax tree, one graph node per ast node, labelled with its operator,
+// literal or name. edges out of ifs and calls say which part they lead to
+pub fn ast(program: &Program) -> String {
+    let mut graph = Graph::new("ast");
+    graph.attributes("node [shape=box, fontname=monospace]");
+
+    let root = graph.node("program");
+    for statement in &program.statements {
+        let child = graph.statement(statement);
+        graph.edge(root, child, None);
+    }
+
+    graph.finish()
+}
+
+// which named functions call which. a function is named by the let it is
+// bound with, and a call counts when its target is a name that leads to such
+// a let, directly or through other lets like `let g = f;`
+pub fn call_graph(program: &Program) -> String {
+    let mut calls = Calls::default();
+
+    calls.begin_scope();
+    calls.visit_program(program);
+    calls.end_scope();
+
+    let mut graph = Graph::new("calls");
+    let nodes: Vec<usize> = calls.functions.iter().map(|x| graph.node(x)).collect();
+
+    for (caller, callee) in calls.edges {
+        graph.edge(nodes[caller], nodes[callee], None);
+    }
+
+    graph.finish()
+}
+
+struct Graph {
+    output: String,
+    nodes: usize,
+}
+
+impl Graph {
+    fn new(name: &str) -> Self {
+        Graph {
+            output: format!("digraph {} {{\n", name),
+            nodes: 0,
+        }
+    }
+
+    fn attributes(&mut self, attributes: &str) {
+        let _ = writeln!(self.output, "    {};", attributes);
+    }
+
+    fn node(&mut self, label: &str) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+
+        let _ = writeln!(self.output, "    n{} [label={}];", id, quote(label));
+        id
+    }
+
+    fn edge(&mut self, from: usize, to: usize, label: Option<&str>) {
+        let _ = match label {
+            Some(label) => writeln!(
+                self.output,
+                "    n{} -> n{} [label={}];",
+                from,
+                to,
+                quote(label)
+            ),
+            None => writeln!(self.output, "    n{} -> n{};", from, to),
+        };
+    }
+
+    fn finish(mut self) -> String {
+        self.output.push('}');
+        self.output
+    }
+
+    fn statement(&mut self, statement: &Statements) -> usize {
+        match statement {
+            Statements::LetStatement(x) => {
+                let id = self.node(&format!("{} {}", x.token.literal, x.name.value));
+                let value = self.expression(&x.value);
+                self.edge(id, value, None);
+                id
+            }
+            Statements::AssignStatement(x) => {
+                let id = self.node(&format!("{} {}", x.name.value, x.operator));
+                let value = self.expression(&x.value);
+                self.edge(id, value, None);
+                id
+            }
+            Statements::ReturnStatement(x) => {
+                let id = self.node("return");
+                let value = self.expression(&x.return_value);
+                self.edge(id, value, None);
+                id
+            }
+            // the statement adds nothing to draw, so it is its expression
+            Statements::ExpressionStatement(x) => self.expression(&x.expression),
+        }
+    }
+
+    fn block(&mut self, from: usize, block: &BlockStatement, label: &str) {
+        let id = self.node("block");
+        self.edge(from, id, Some(label));
+
+        for statement in &block.statements {
+            let child = self.statement(statement);
+            self.edge(id, child, None);
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) -> usize {
+        match expression {
+            Expression::Identifier(x) => self.node(&x.value),
+            Expression::IntegerLiteral(x) => self.node(&x.token.literal),
+            Expression::BigIntLiteral(x) => self.node(&x.token.literal),
+            Expression::Boolean(x) => self.node(&x.token.literal),
+            Expression::NullLiteral(x) => self.node(&x.token.literal),
+            Expression::PrefixExpression(x) => {
+                let id = self.node(&x.operator);
+                let right = self.expression(&x.right);
+                self.edge(id, right, None);
+                id
+            }
+            Expression::InfixExpression(x) => {
+                let id = self.node(&x.operator);
+                let left = self.expression(&x.left);
+                self.edge(id, left, None);
+                let right = self.expression(&x.right);
+                self.edge(id, right, None);
+                id
+            }
+            Expression::IfExpression(x) => {
+                let id = self.node("if");
+                let condition = self.expression(&x.condition);
+                self.edge(id, condition, Some("condition"));
+                self.block(id, &x.consequence, "then");
+
+                if let Some(alternative) = &x.alternative {
+                    self.block(id, alternative, "else");
+                }
+                id
+            }
+            Expression::FunctionLiteral(x) => {
+                let parameters: Vec<&str> = x.parameters.iter().map(|x| x.value.as_str()).collect();
+                let id = self.node(&format!("fn({})", parameters.join(", ")));
+                self.block(id, &x.body, "body");
+                id
+            }
+            Expression::CallExpression(x) => {
+                let id = self.node("call");
+                let function = self.expression(&x.function);
+                self.edge(id, function, Some("function"));
+
+                for (i, argument) in x.arguments.iter().enumerate() {
+                    let argument = self.expression(argument);
+                    self.edge(id, argument, Some(&i.to_string()));
+                }
+                id
+            }
+            Expression::ILLEGAL => self.node("ILLEGAL"),
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Default)]
+struct CallScope {
+    // None for names bound to anything but a named function
+    names: HashMap<String, Option<usize>>,
+    // as in the resolver, bodies wait until every name in the scope is bound
+    pending: Vec<(FunctionLiteral, Option<usize>)>,
+}
+
+#[derive(Default)]
+struct Calls {
+    functions: Vec<String>,
+    edges: Vec<(usize, usize)>,
+    scopes: Vec<CallScope>,
+    // the named function whose body is being walked
+    caller: Option<usize>,
+}
+
+impl Calls {
+    fn begin_scope(&mut self) {
+        self.scopes.push(CallScope::default());
+    }
+
+    fn end_scope(&mut self) {
+        let pending = std::mem::take(&mut self.current_scope().pending);
+        for (function, caller) in &pending {
+            self.visit_body(function, *caller);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn current_scope(&mut self) -> &mut CallScope {
+        self.scopes.last_mut().expect("call graph has no scope")
+    }
+
+    fn declare(&mut self, name: &str, function: Option<usize>) {
+        self.current_scope()
+            .names
+            .insert(name.to_string(), function);
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.names.get(name))
+            .cloned()
+            .flatten()
+    }
+
+    fn visit_body(&mut self, function: &FunctionLiteral, caller: Option<usize>) {
+        let outer = std::mem::replace(&mut self.caller, caller);
+        self.begin_scope();
+
+        for parameter in &function.parameters {
+            self.declare(&parameter.value, None);
+        }
+
+        walk_block(self, &function.body);
+        self.end_scope();
+        self.caller = outer;
+    }
+}
+
+impl Visitor for Calls {
+    fn visit_let_statement(&mut self, statement: &LetStatement) {
+        let name = &statement.name.value;
+
+        match &statement.value {
+            Expression::FunctionLiteral(x) => {
+                let id = self.functions.len();
+                self.functions.push(name.clone());
+                self.current_scope().pending.push((x.clone(), Some(id)));
+                self.declare(name, Some(id));
+            }
+            Expression::Identifier(x) => {
+                let function = self.lookup(&x.value);
+                self.declare(name, function);
+            }
+            value => {
+                self.visit_expression(value);
+                self.declare(name, None);
+            }
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression) {
+        if let Expression::Identifier(x) = call.function.as_ref() {
+            if let (Some(caller), Some(callee)) = (self.caller, self.lookup(&x.value)) {
+                if !self.edges.contains(&(caller, callee)) {
+                    self.edges.push((caller, callee));
+                }
+            }
+        }
+
+        walk_call_expression(self, call);
+    }
+
+    // calls in an anonymous function count for the function it is written in
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        let caller = self.caller;
+        self.current_scope()
+            .pending
+            .push((function.clone(), caller));
+    }
+}
@@ -1,84 +1,618 @@
+use crate::diagnostics::Diagnostic;
+use crate::interrupt;
+use crate::lexer::token::{Span, KEYWORDS};
 use crate::object::*;
 use crate::parser::ast::expressions::*;
 use crate::parser::ast::statements::*;
 use crate::parser::ast::*;
-use std::io::Result;
+use crate::utils::suggest;
+use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
+use std::fmt;
+use std::rc::Rc;
 
-pub fn eval(node: &impl Node) -> Result<Object> {
+const IDENTIFIER_NOT_FOUND: &str = "E0100";
+const NOT_A_FUNCTION: &str = "E0101";
+const WRONG_NUMBER_OF_ARGUMENTS: &str = "E0102";
+const UNKNOWN_OPERATOR: &str = "E0103";
+const INTERRUPTED: &str = "E0104";
+const NEGATIVE_OPERAND: &str = "E0105";
+const DIVISION_BY_ZERO: &str = "E0106";
+const INTEGER_OVERFLOW: &str = "E0107";
+const TYPE_MISMATCH: &str = "E0108";
+const UNDECLARED_ASSIGNMENT: &str = "E0109";
+const ASSIGNMENT_TO_CONSTANT: &str = "E0110";
+const REDECLARED_CONSTANT: &str = "E0111";
+const RECURSION_LIMIT: &str = "E0112";
+const EXPRESSION_TOO_DEEP: &str = "E0113";
+
+// integers beyond 64 bits grow up to this many digits, so that a runaway
+// computation fails instead of eating all memory
+pub const MAX_DIGITS: usize = 10_000;
+
+// calls nest at most this deep, so that runaway recursion fails instead of
+// overflowing the native stack
+pub const MAX_CALL_DEPTH: usize = 1_000;
+
+// expressions being evaluated nest at most this deep, counted across calls.
+// the parser keeps each expression it reads shallow, but calls stack them up
+pub const MAX_EVAL_DEPTH: usize = 20_000;
+
+// native stack to evaluate on. MAX_EVAL_DEPTH expressions take about half of it
+// in a debug build
+pub const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+// a traceback shows this many of the innermost calls
+pub const MAX_TRACE_FRAMES: usize = 10;
+
+thread_local! {
+    // the calls being evaluated, outermost first
+    static CALL_STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+    // how many expressions the current one is nested in
+    static EVAL_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+// a function call the error propagated through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub name: Option<String>,
+    pub call_site: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeError {
+    pub diagnostic: Box<Diagnostic>,
+    // innermost call first, cut down to MAX_TRACE_FRAMES
+    pub trace: Vec<Frame>,
+    // outer calls left out of the trace
+    pub omitted_frames: usize,
+}
+
+impl RuntimeError {
+    pub fn is_interrupted(&self) -> bool {
+        self.diagnostic.code == INTERRUPTED
+    }
+
+    // the diagnostic followed by the traceback
+    pub fn render(&self, source: &str, file_name: &str, color: bool) -> String {
+        let mut output = self.diagnostic.render(source, file_name, color);
+
+        for frame in &self.trace {
+            output.push_str(&format!(
+                "  at {} ({}:{}:{})\n",
+                frame.name.as_deref().unwrap_or("<anonymous>"),
+                file_name,
+                frame.call_site.line,
+                frame.call_site.column
+            ));
+        }
+
+        if self.omitted_frames > 0 {
+            output.push_str(&format!("  ... {} more\n", self.omitted_frames));
+        }
+
+        output
+    }
+}
+
+impl From<Diagnostic> for RuntimeError {
+    fn from(diagnostic: Diagnostic) -> RuntimeError {
+        RuntimeError {
+            diagnostic: Box::new(diagnostic),
+            trace: Vec::new(),
+            omitted_frames: 0,
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.diagnostic)
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+pub type Result<T> = std::result::Result<T, RuntimeError>;
+
+// evaluates any node. the evaluator itself recurses on references, since
+// to_ast clones the whole subtree of the node
+pub fn eval(node: &impl Node, env: &Rc<RefCell<Environment>>) -> Result<Object> {
     match node.to_ast() {
-        AST::Program(x) => eval_statement(x.statements),
-        AST::ExpressionStatement(x) => eval(&x.expression),
-        AST::PrefixExpression(x) => eval_prefix_expression(&x),
-        AST::InfixExpression(x) => eval_infix_expression(&x),
-        AST::BlockStatement(x) => eval_statement(x.statements),
-        AST::IfExpression(x) => eval_if_expression(&x),
-        AST::IntegerLiteral(x) => Ok(Object {
+        AST::Program(x) => eval_program(&x.statements, env),
+        AST::LetStatement(x) => eval_statement(&Statements::LetStatement(x), env),
+        AST::AssignStatement(x) => eval_statement(&Statements::AssignStatement(x), env),
+        AST::ReturnStatement(x) => eval_statement(&Statements::ReturnStatement(x), env),
+        AST::ExpressionStatement(x) => eval_statement(&Statements::ExpressionStatement(x), env),
+        AST::BlockStatement(x) => eval_block_statement(&x.statements, env),
+        AST::Identifier(x) => eval_expression(&Expression::Identifier(x), env),
+        AST::PrefixExpression(x) => eval_expression(&Expression::PrefixExpression(x), env),
+        AST::InfixExpression(x) => eval_expression(&Expression::InfixExpression(x), env),
+        AST::IfExpression(x) => eval_expression(&Expression::IfExpression(x), env),
+        AST::FunctionLiteral(x) => eval_expression(&Expression::FunctionLiteral(x), env),
+        AST::CallExpression(x) => eval_expression(&Expression::CallExpression(x), env),
+        AST::IntegerLiteral(x) => eval_expression(&Expression::IntegerLiteral(x), env),
+        AST::BigIntLiteral(x) => eval_expression(&Expression::BigIntLiteral(x), env),
+        AST::Boolean(x) => eval_expression(&Expression::Boolean(x), env),
+        AST::NullLiteral(x) => eval_expression(&Expression::NullLiteral(x), env),
+    }
+}
+
+fn eval_statement(statement: &Statements, env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    match statement {
+        Statements::LetStatement(x) => eval_let_statement(x, env),
+        Statements::AssignStatement(x) => eval_assign_statement(x, env),
+        Statements::ReturnStatement(x) => Ok(Object {
+            object_type: ObjectType::ReturnValue(Box::new(eval_expression(&x.return_value, env)?)),
+        }),
+        Statements::ExpressionStatement(x) => eval_expression(&x.expression, env),
+    }
+}
+
+fn eval_expression(expression: &Expression, env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    if interrupt::is_interrupted() {
+        return Err(Diagnostic::error(INTERRUPTED, "interrupted").into());
+    }
+
+    enter_expression(expression)?;
+    let result = match expression {
+        Expression::Identifier(x) => eval_identifier(x, env),
+        Expression::PrefixExpression(x) => eval_prefix_expression(x, env),
+        Expression::InfixExpression(x) => eval_infix_expression(x, env),
+        Expression::IfExpression(x) => eval_if_expression(x, env),
+        Expression::FunctionLiteral(x) => Ok(eval_function_literal(x, env)),
+        Expression::CallExpression(x) => eval_call_expression(x, env),
+        Expression::IntegerLiteral(x) => Ok(Object {
             object_type: ObjectType::Integer(x.value),
         }),
-        AST::Boolean(x) => Ok(Object {
+        Expression::BigIntLiteral(x) => Ok(Object::from_big_int(x.value.clone())),
+        Expression::Boolean(x) => Ok(Object {
             object_type: ObjectType::Boolean(x.value),
         }),
-        _ => unimplemented!(),
-    }
+        Expression::NullLiteral(_) => Ok(NULL),
+        Expression::ILLEGAL => unreachable!("illegal expressions do not parse"),
+    };
+    leave_expression();
+
+    result
 }
 
-fn eval_statement(stmts: Vec<Statements>) -> Result<Object> {
-    let mut result = Ok(Object {
-        object_type: ObjectType::Null,
-    });
+fn eval_program(stmts: &[Statements], env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    let result = eval_block_statement(stmts, env)?;
+
+    Ok(unwrap_return_value(result))
+}
+
+// a return value is passed through as is so that the enclosing function can unwrap it
+fn eval_block_statement(stmts: &[Statements], env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    let mut result = NULL;
 
     for stmt in stmts {
-        result = match stmt {
-            Statements::ExpressionStatement(x) => eval(&x),
-            _ => panic!(),
+        result = eval_statement(stmt, env)?;
+
+        if result.is_return_value() {
+            break;
         }
     }
 
-    result
+    Ok(result)
+}
+
+fn eval_let_statement(statement: &LetStatement, env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    let name = &statement.name;
+
+    let declaration = env.borrow().local_constant(&name.value);
+    if let Some(declaration) = declaration {
+        return Err(redeclared_constant(name, declaration));
+    }
+
+    let mut value = eval_expression(&statement.value, env)?;
+
+    if let ObjectType::Function(ref mut function) = value.object_type {
+        if function.name.is_none() {
+            Rc::make_mut(function).name = Some(name.value.clone());
+        }
+    }
+
+    if statement.is_const() {
+        env.borrow_mut()
+            .set_constant(name.value.clone(), value, name.token.span);
+    } else {
+        env.borrow_mut().set(name.value.clone(), value);
+    }
+    Ok(NULL)
+}
+
+// errors are built outside the functions that raise them, so that their
+// temporaries do not grow the stack frame of every evaluation
+
+fn redeclared_constant(name: &Identifier, declaration: Span) -> RuntimeError {
+    Diagnostic::error(
+        REDECLARED_CONSTANT,
+        format!("redeclaration of constant: {}", name.value),
+    )
+    .with_primary(name.token.span, "redeclared here")
+    .with_secondary(declaration, "declared as constant here")
+    .into()
+}
+
+// `x += value` is `x = x + value`, with any error pointing at the whole statement
+fn eval_assign_statement(
+    statement: &AssignStatement,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let name = &statement.name;
+
+    if !env.borrow().contains(&name.value) {
+        return Err(undeclared_assignment(name, env));
+    }
+
+    let declaration = env.borrow().constant(&name.value);
+    if let Some(declaration) = declaration {
+        return Err(assignment_to_constant(name, declaration));
+    }
+
+    let value = match statement.operator.trim_end_matches('=') {
+        "" => eval_expression(&statement.value, env)?,
+        operator => eval_infix_expression(
+            &InfixExpression {
+                token: statement.token.clone(),
+                operator: operator.to_string(),
+                left: Box::new(Expression::Identifier(name.clone())),
+                right: Box::new(statement.value.clone()),
+            },
+            env,
+        )?,
+    };
+
+    env.borrow_mut().assign(&name.value, value);
+    Ok(NULL)
+}
+
+fn undeclared_assignment(name: &Identifier, env: &Rc<RefCell<Environment>>) -> RuntimeError {
+    let diagnostic = Diagnostic::error(
+        UNDECLARED_ASSIGNMENT,
+        format!("assignment to undeclared variable: {}", name.value),
+    )
+    .with_primary(name.token.span, "not declared in this scope");
+
+    let names = env.borrow().names();
+    let diagnostic = match suggest(&name.value, names.iter().map(|x| x.as_str())) {
+        Some(suggestion) => diagnostic.with_help(format!("did you mean `{}`?", suggestion)),
+        None => diagnostic.with_help(format!("declare it first with `let {} = ...`", name.value)),
+    };
+
+    diagnostic.into()
+}
+
+fn assignment_to_constant(name: &Identifier, declaration: Span) -> RuntimeError {
+    Diagnostic::error(
+        ASSIGNMENT_TO_CONSTANT,
+        format!("assignment to constant: {}", name.value),
+    )
+    .with_primary(name.token.span, "cannot assign to a constant")
+    .with_secondary(declaration, "declared as constant here")
+    .with_help(format!(
+        "declare it with `let {} = ...` if it needs to change",
+        name.value
+    ))
+    .into()
+}
+
+fn eval_identifier(identifier: &Identifier, env: &Rc<RefCell<Environment>>) -> Result<Object> {
+    match env.borrow().get(&identifier.value) {
+        Some(x) => Ok(x),
+        None => Err(identifier_not_found(identifier, env)),
+    }
 }
 
-fn eval_if_expression(if_expression: &IfExpression) -> Result<Object> {
-    let condition = eval(&*if_expression.condition)?;
+fn identifier_not_found(identifier: &Identifier, env: &Rc<RefCell<Environment>>) -> RuntimeError {
+    let mut diagnostic = Diagnostic::error(
+        IDENTIFIER_NOT_FOUND,
+        format!("identifier not found: {}", identifier.value),
+    )
+    .with_primary(identifier.token.span, "not found in this scope");
+
+    let names = env.borrow().names();
+    let candidates = names
+        .iter()
+        .map(|x| x.as_str())
+        .chain(KEYWORDS.iter().map(|&(x, _)| x));
+    if let Some(suggestion) = suggest(&identifier.value, candidates) {
+        diagnostic = diagnostic.with_help(format!("did you mean `{}`?", suggestion));
+    }
+
+    diagnostic.into()
+}
+
+fn eval_function_literal(function: &FunctionLiteral, env: &Rc<RefCell<Environment>>) -> Object {
+    Object {
+        object_type: ObjectType::Function(Rc::new(Function {
+            parameters: function.parameters.clone(),
+            body: function.body.clone(),
+            env: Rc::clone(env),
+            span: function.token.span,
+            name: None,
+        })),
+    }
+}
+
+fn eval_call_expression(
+    call_expression: &CallExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let function = match eval_expression(&call_expression.function, env)?.object_type {
+        ObjectType::Function(x) => x,
+        x => return Err(not_a_function(call_expression, Object { object_type: x })),
+    };
+
+    if function.parameters.len() != call_expression.arguments.len() {
+        return Err(wrong_number_of_arguments(call_expression, &function));
+    }
+
+    let function_env = Environment::new_enclosed(&function.env);
+    for (parameter, argument) in function.parameters.iter().zip(&call_expression.arguments) {
+        let value = eval_expression(argument, env)?;
+        function_env
+            .borrow_mut()
+            .set(parameter.value.clone(), value);
+    }
+
+    enter_call(Frame {
+        name: function.name.clone(),
+        call_site: call_expression.function.span(),
+    })?;
+    let result = eval_block_statement(&function.body.statements, &function_env);
+    let result = result.map_err(with_trace);
+    leave_call();
+
+    Ok(unwrap_return_value(result?))
+}
+
+fn not_a_function(call_expression: &CallExpression, value: Object) -> RuntimeError {
+    Diagnostic::error(
+        NOT_A_FUNCTION,
+        format!("not a function: {}", value.inspect()),
+    )
+    .with_primary(call_expression.function.span(), "called here")
+    .into()
+}
+
+fn wrong_number_of_arguments(
+    call_expression: &CallExpression,
+    function: &Function,
+) -> RuntimeError {
+    let expected = function.parameters.len();
+
+    Diagnostic::error(
+        WRONG_NUMBER_OF_ARGUMENTS,
+        format!(
+            "wrong number of arguments: expected {}, got {}",
+            expected,
+            call_expression.arguments.len()
+        ),
+    )
+    .with_primary(
+        call_expression
+            .function
+            .span()
+            .to(&call_expression.token.span),
+        format!(
+            "expected {} argument{}",
+            expected,
+            if expected == 1 { "" } else { "s" }
+        ),
+    )
+    .with_secondary(function.span, "function defined here")
+    .into()
+}
+
+fn enter_call(frame: Frame) -> Result<()> {
+    let depth = CALL_STACK.with(|stack| stack.borrow().len());
+
+    if depth >= MAX_CALL_DEPTH {
+        let diagnostic = Diagnostic::error(
+            RECURSION_LIMIT,
+            format!("maximum call depth of {} exceeded", MAX_CALL_DEPTH),
+        )
+        .with_primary(frame.call_site, "one call too many")
+        .with_help("check that the recursion reaches its base case");
+        return Err(with_trace(diagnostic.into()));
+    }
+
+    CALL_STACK.with(|stack| stack.borrow_mut().push(frame));
+    Ok(())
+}
+
+fn leave_call() {
+    CALL_STACK.with(|stack| stack.borrow_mut().pop());
+}
+
+fn enter_expression(expression: &Expression) -> Result<()> {
+    let depth = EVAL_DEPTH.with(|x| x.get());
+
+    if depth >= MAX_EVAL_DEPTH {
+        let diagnostic = Diagnostic::error(
+            EXPRESSION_TOO_DEEP,
+            format!("maximum expression depth of {} exceeded", MAX_EVAL_DEPTH),
+        )
+        .with_primary(expression.span(), "nested too deep")
+        .with_help("check that the recursion reaches its base case");
+        return Err(with_trace(diagnostic.into()));
+    }
+
+    EVAL_DEPTH.with(|x| x.set(depth + 1));
+    Ok(())
+}
+
+fn leave_expression() {
+    EVAL_DEPTH.with(|x| x.set(x.get() - 1));
+}
+
+// the calls an error raised now propagates through. the innermost call to see
+// the error records them, the ones around it keep what it recorded
+fn with_trace(mut error: RuntimeError) -> RuntimeError {
+    if !error.trace.is_empty() {
+        return error;
+    }
+
+    CALL_STACK.with(|stack| {
+        let stack = stack.borrow();
+        error.trace = stack.iter().rev().take(MAX_TRACE_FRAMES).cloned().collect();
+        error.omitted_frames = stack.len().saturating_sub(MAX_TRACE_FRAMES);
+    });
+    error
+}
+
+fn unwrap_return_value(object: Object) -> Object {
+    match object.object_type {
+        ObjectType::ReturnValue(x) => *x,
+        _ => object,
+    }
+}
+
+fn eval_if_expression(
+    if_expression: &IfExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let condition = eval_expression(&if_expression.condition, env)?;
 
     if condition.is_truthy() {
-        eval(&if_expression.consequence)
+        eval_block_statement(&if_expression.consequence.statements, env)
     } else if let Some(alternative) = &if_expression.alternative {
-        eval(alternative)
+        eval_block_statement(&alternative.statements, env)
     } else {
         Ok(NULL)
     }
 }
 
-fn eval_prefix_expression(prefix_expression: &PrefixExpression) -> Result<Object> {
-    let right = eval(&*prefix_expression.right)?;
+fn eval_prefix_expression(
+    prefix_expression: &PrefixExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let right = eval_expression(&prefix_expression.right, env)?;
 
-    match prefix_expression.operator.as_str() {
-        "!" => Ok(eval_bang_operator(&right)),
-        "-" => Ok(eval_minus_prefix(&right)),
-        _ => panic!(),
-    }
+    let evaluated = match prefix_expression.operator.as_str() {
+        "!" => Some(eval_bang_operator(&right)),
+        "-" => eval_minus_prefix(&right),
+        "~" => eval_tilde_prefix(&right),
+        _ => None,
+    };
+
+    evaluated.ok_or_else(|| {
+        Diagnostic::error(
+            UNKNOWN_OPERATOR,
+            format!(
+                "unknown operator: {}{}",
+                prefix_expression.operator,
+                right.type_name()
+            ),
+        )
+        .with_primary(
+            prefix_expression
+                .token
+                .span
+                .to(&prefix_expression.right.span()),
+            format!("cannot apply {} to this", prefix_expression.operator),
+        )
+        .into()
+    })
 }
 
-fn eval_infix_expression(infix_expression: &InfixExpression) -> Result<Object> {
-    let right = eval(&*infix_expression.right)?;
-    let left = eval(&*infix_expression.left)?;
+fn eval_infix_expression(
+    infix_expression: &InfixExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    match infix_expression.operator.as_str() {
+        "&&" | "||" => return eval_logical_expression(infix_expression, env),
+        "??" => return eval_nullish_expression(infix_expression, env),
+        _ => (),
+    }
+
+    // left to right, as an assignment on one side can be seen by the other
+    let left = eval_expression(&infix_expression.left, env)?;
+    let right = eval_expression(&infix_expression.right, env)?;
 
     if right.is_int() && left.is_int() {
-        return Ok(eval_integer_infix_expression(
-            &infix_expression.operator,
-            &right,
-            &left,
-        ));
+        return eval_integer_infix_expression(infix_expression, &right, &left);
     }
 
+    if let (Some(right), Some(left)) = (right.big_int_value(), left.big_int_value()) {
+        return eval_big_int_infix_expression(infix_expression, &right, &left);
+    }
+
+    // only integers have an order, so anything else but == and != fails
     match infix_expression.operator.as_str() {
-        "==" => Ok(Object::from_bool(
-            left.boolean_value() == right.boolean_value(),
-        )),
-        "!=" => Ok(Object::from_bool(
-            left.boolean_value() != right.boolean_value(),
+        "==" => Ok(Object::from_bool(left.equals(&right))),
+        "!=" => Ok(Object::from_bool(!left.equals(&right))),
+        _ => Err(invalid_operands(infix_expression, &left, &right)),
+    }
+}
+
+fn invalid_operands(
+    infix_expression: &InfixExpression,
+    left: &Object,
+    right: &Object,
+) -> RuntimeError {
+    let operator = infix_expression.operator.as_str();
+    let (code, message) = if left.type_name() != right.type_name() {
+        (TYPE_MISMATCH, "type mismatch")
+    } else {
+        (UNKNOWN_OPERATOR, "unknown operator")
+    };
+
+    Diagnostic::error(
+        code,
+        format!(
+            "{}: {} {} {}",
+            message,
+            left.type_name(),
+            operator,
+            right.type_name()
+        ),
+    )
+    .with_primary(
+        infix_expression
+            .left
+            .span()
+            .to(&infix_expression.right.span()),
+        format!(
+            "cannot apply {} to {} and {}",
+            operator,
+            left.type_name(),
+            right.type_name()
+        ),
+    )
+    .into()
+}
+
+// the right side only runs when the left one does not decide the result,
+// which is always a boolean
+fn eval_logical_expression(
+    infix_expression: &InfixExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let left = eval_expression(&infix_expression.left, env)?.is_truthy();
+
+    match (infix_expression.operator.as_str(), left) {
+        ("&&", false) => Ok(FALSE),
+        ("||", true) => Ok(TRUE),
+        _ => Ok(Object::from_bool(
+            eval_expression(&infix_expression.right, env)?.is_truthy(),
         )),
-        _ => Ok(NULL),
+    }
+}
+
+// the left side unless it is null, in which case the right side runs
+fn eval_nullish_expression(
+    infix_expression: &InfixExpression,
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Object> {
+    let left = eval_expression(&infix_expression.left, env)?;
+
+    match left.object_type {
+        ObjectType::Null => eval_expression(&infix_expression.right, env),
+        _ => Ok(left),
     }
 }
 
@@ -91,29 +625,208 @@ fn eval_bang_operator(right: &Object) -> Object {
     }
 }
 
-fn eval_minus_prefix(right: &Object) -> Object {
+fn eval_minus_prefix(right: &Object) -> Option<Object> {
     match right.object_type {
-        ObjectType::Integer(i) => Object {
-            object_type: ObjectType::Integer(-i),
-        },
-        ObjectType::Null => NULL,
-        _ => panic!("faild: eval minus prefix"),
+        ObjectType::Integer(i) => Some(match i.checked_neg() {
+            Some(x) => Object::from_int(x),
+            None => Object::from_big_int(-&BigInt::from_i64(i)),
+        }),
+        ObjectType::BigInt(ref x) => Some(Object::from_big_int(-x)),
+        ObjectType::Null => Some(NULL),
+        _ => None,
+    }
+}
+
+fn eval_tilde_prefix(right: &Object) -> Option<Object> {
+    match right.object_type {
+        ObjectType::Integer(i) => Some(Object::from_int(!i)),
+        _ => None,
     }
 }
 
-fn eval_integer_infix_expression(operator: &str, right: &Object, left: &Object) -> Object {
-    match operator {
-        // integer operator
-        "+" => Object::from_int(left.integer_value() + right.integer_value()),
-        "-" => Object::from_int(left.integer_value() - right.integer_value()),
-        "*" => Object::from_int(left.integer_value() * right.integer_value()),
-        "/" => Object::from_int(left.integer_value() / right.integer_value()),
+// exponents too large for pow only leave 0, 1 and -1 in range
+fn checked_pow(base: i64, exponent: i64) -> Option<i64> {
+    match base {
+        0 | 1 => Some(if exponent == 0 { 1 } else { base }),
+        -1 => Some(if exponent % 2 == 0 { 1 } else { -1 }),
+        _ => base.checked_pow(u32::try_from(exponent).ok()?),
+    }
+}
+
+fn eval_integer_infix_expression(
+    infix_expression: &InfixExpression,
+    right: &Object,
+    left: &Object,
+) -> Result<Object> {
+    let (left, right) = (left.integer_value(), right.integer_value());
+    let operator = infix_expression.operator.as_str();
+
+    check_right_operand(infix_expression, &right, right < 0, right == 0)?;
+
+    // a result that does not fit is computed again as a big integer, the same
+    // in debug and release builds
+    let checked = match operator {
+        "+" => Some(left.checked_add(right)),
+        "-" => Some(left.checked_sub(right)),
+        "*" => Some(left.checked_mul(right)),
+        "/" => Some(left.checked_div(right)),
+        "%" => Some(left.checked_rem(right)),
+        "**" => Some(checked_pow(left, right)),
+        _ => None,
+    };
+
+    match checked {
+        Some(Some(result)) => return Ok(Object::from_int(result)),
+        Some(None) => {
+            return eval_big_int_infix_expression(
+                infix_expression,
+                &BigInt::from_i64(right),
+                &BigInt::from_i64(left),
+            )
+        }
+        None => (),
+    }
+
+    let evaluated = match operator {
+        // bitwise operator. shifting by 64 or more moves every bit out
+        "&" => Object::from_int(left & right),
+        "|" => Object::from_int(left | right),
+        "^" => Object::from_int(left ^ right),
+        "<<" if right >= 64 => Object::from_int(0),
+        "<<" => Object::from_int(left << right),
+        ">>" => Object::from_int(left >> right.min(63)),
 
         // boolean operator
-        "<" => Object::from_bool(left.integer_value() < right.integer_value()),
-        ">" => Object::from_bool(left.integer_value() > right.integer_value()),
-        "==" => Object::from_bool(left.integer_value() == right.integer_value()),
-        "!=" => Object::from_bool(left.integer_value() != right.integer_value()),
+        "<" => Object::from_bool(left < right),
+        ">" => Object::from_bool(left > right),
+        "<=" => Object::from_bool(left <= right),
+        ">=" => Object::from_bool(left >= right),
+        "==" => Object::from_bool(left == right),
+        "!=" => Object::from_bool(left != right),
         _ => NULL,
+    };
+
+    Ok(evaluated)
+}
+
+// at least one operand does not fit in 64 bits, or the result did not. the
+// bitwise operators only work on 64 bits
+fn eval_big_int_infix_expression(
+    infix_expression: &InfixExpression,
+    right: &BigInt,
+    left: &BigInt,
+) -> Result<Object> {
+    let operator = infix_expression.operator.as_str();
+
+    check_right_operand(
+        infix_expression,
+        right,
+        right.is_negative(),
+        right.is_zero(),
+    )?;
+
+    let result = match operator {
+        "+" => Some(left + right),
+        "-" => Some(left - right),
+        "*" => Some(left * right),
+        "/" => left.div_rem(right).map(|(quotient, _)| quotient),
+        "%" => left.div_rem(right).map(|(_, remainder)| remainder),
+        "**" => match right.to_i64() {
+            Some(x) => left.checked_pow(x as u64, MAX_DIGITS),
+            // exponents beyond 64 bits only leave 0, 1 and -1 in range, whose
+            // powers only depend on whether the exponent is odd
+            None if matches!(left.to_i64(), Some(-1..=1)) => {
+                let (_, odd) = right
+                    .div_rem(&BigInt::from_i64(2))
+                    .expect("two is not zero");
+                left.checked_pow(if odd.is_zero() { 2 } else { 1 }, MAX_DIGITS)
+            }
+            None => None,
+        },
+
+        "<" => return Ok(Object::from_bool(left < right)),
+        ">" => return Ok(Object::from_bool(left > right)),
+        "<=" => return Ok(Object::from_bool(left <= right)),
+        ">=" => return Ok(Object::from_bool(left >= right)),
+        "==" => return Ok(Object::from_bool(left == right)),
+        "!=" => return Ok(Object::from_bool(left != right)),
+
+        _ => {
+            let type_name = |x: &BigInt| Object::from_big_int(x.clone()).type_name();
+
+            return Err(Diagnostic::error(
+                UNKNOWN_OPERATOR,
+                format!(
+                    "unknown operator: {} {} {}",
+                    type_name(left),
+                    operator,
+                    type_name(right)
+                ),
+            )
+            .with_primary(
+                infix_expression
+                    .left
+                    .span()
+                    .to(&infix_expression.right.span()),
+                format!("{} only works on 64 bit integers", operator),
+            )
+            .into());
+        }
+    };
+
+    match result {
+        Some(x) if x.digits() <= MAX_DIGITS => Ok(Object::from_big_int(x)),
+        _ => Err(Diagnostic::error(
+            INTEGER_OVERFLOW,
+            format!(
+                "integer overflow: the result of {} has more than {} digits",
+                operator, MAX_DIGITS
+            ),
+        )
+        .with_primary(
+            infix_expression
+                .left
+                .span()
+                .to(&infix_expression.right.span()),
+            "the result is too large",
+        )
+        .into()),
     }
 }
+
+// errors that depend only on the right operand, whichever way it is stored
+fn check_right_operand(
+    infix_expression: &InfixExpression,
+    right: &dyn fmt::Display,
+    negative: bool,
+    zero: bool,
+) -> Result<()> {
+    let operator = infix_expression.operator.as_str();
+
+    if negative && matches!(operator, "**" | "<<" | ">>") {
+        let what = if operator == "**" {
+            "exponent"
+        } else {
+            "shift amount"
+        };
+
+        return Err(
+            Diagnostic::error(NEGATIVE_OPERAND, format!("negative {}: {}", what, right))
+                .with_primary(
+                    infix_expression.right.span(),
+                    format!("the {} must not be negative", what),
+                )
+                .into(),
+        );
+    }
+
+    if zero && matches!(operator, "/" | "%") {
+        return Err(
+            Diagnostic::error(DIVISION_BY_ZERO, "attempt to divide by zero")
+                .with_primary(infix_expression.right.span(), "this is zero")
+                .into(),
+        );
+    }
+
+    Ok(())
+}
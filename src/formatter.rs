@@ -0,0 +1,66 @@
+use crate::diagnostics::Diagnostic;
+use crate::lexer::token::TokenType;
+use crate::lexer::Lexer;
+use crate::parser::ast::printer::{print_with_layout, Comment, Layout};
+use crate::parser::parse;
+
+// reprints source in the one house style, keeping its comments and single
+// blank lines between statements
+pub fn format(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let program = parse(source)?;
+
+    Ok(print_with_layout(&program, layout(source)))
+}
+
+pub fn layout(source: &str) -> Layout {
+    let mut layout = Layout::default();
+
+    for (i, line) in source.split('\n').enumerate() {
+        if line.trim().is_empty() {
+            layout.blank_lines.insert(i + 1);
+        }
+    }
+
+    // comments come from the lexer, so a // inside another token is not one
+    let mut lexer = Lexer::new(&source).with_trivia();
+    let mut open_braces = Vec::new();
+    let mut open_parens = Vec::new();
+    // line of the last token that is not whitespace or a comment
+    let mut code_line = 0;
+
+    loop {
+        let token = lexer.next_token();
+        let span = token.span;
+
+        match token.token_type {
+            TokenType::WHITESPACE => continue,
+            TokenType::COMMENT => {
+                layout.comments.push(Comment {
+                    start: span.start,
+                    line: span.line,
+                    text: token.literal.trim_end().to_string(),
+                    trailing: code_line == span.line,
+                });
+                continue;
+            }
+            TokenType::LBRACE => open_braces.push(span.start),
+            TokenType::RBRACE => {
+                if let Some(start) = open_braces.pop() {
+                    layout.closing_braces.insert(start, span.start);
+                }
+            }
+            TokenType::LPAREN => open_parens.push(span.start),
+            TokenType::RPAREN => {
+                if let Some(start) = open_parens.pop() {
+                    layout.opening_parens.insert(span.start, start);
+                }
+            }
+            TokenType::EOF => break,
+            _ => (),
+        }
+
+        code_line = span.line;
+    }
+
+    layout
+}
@@ -0,0 +1,37 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// set by the SIGINT handler and polled by the evaluator
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+const SIGINT: i32 = 2;
+
+#[cfg(unix)]
+extern "C" {
+    fn signal(signum: i32, handler: usize) -> usize;
+}
+
+#[cfg(unix)]
+extern "C" fn handle_sigint(_: i32) {
+    interrupt();
+}
+
+// makes Ctrl-C raise the interrupt flag instead of killing the process
+pub fn install_handler() {
+    #[cfg(unix)]
+    unsafe {
+        signal(SIGINT, handle_sigint as *const () as usize);
+    }
+}
+
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+pub fn clear() {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+}
@@ -0,0 +1,298 @@
+use crate::diagnostics::Diagnostic;
+use crate::lexer::token::{Span, Token, TokenType};
+use crate::lexer::Lexer;
+use crate::parser::ast::expressions::*;
+use crate::parser::ast::statements::*;
+use crate::parser::ast::Program;
+use crate::parser::parse;
+use std::fmt;
+
+// json export of tokens and programs, for tools not written in rust.
+// the document `export` writes is
+//
+//     {"version": 1, "tokens": [token...], "program": node}
+//
+// a token is {"type": "LET", "literal": "let", "span": span}, with the type
+// named as in TokenType, and a span is {"start", "end", "line", "column"},
+// offsets counting chars from 0, lines and columns from 1.
+//
+// a node is an object whose "kind" is one of the names below. every node but
+// Program has the "span" of the token it was parsed from: the keyword of a
+// let or return, the first token of an expression statement, `if` or `fn`,
+// the operator of an assignment, prefix or infix expression, the `)` closing a call, the
+// `{` of a block, or the name or literal itself.
+//
+//     Program              statements: [statement]
+//     LetStatement         name: Identifier, value: expression, constant: bool
+//     AssignStatement      name: Identifier, operator: string, value: expression
+//     ReturnStatement      value: expression
+//     ExpressionStatement  expression: expression
+//     Block                statements: [statement]
+//     Identifier           name: string
+//     IntegerLiteral       value: number
+//     BigIntLiteral        value: string of decimal digits, for integers beyond 64 bits
+//     Boolean              value: bool
+//     NullLiteral
+//     PrefixExpression     operator: string, right: expression
+//     InfixExpression      operator: string, left: expression, right: expression
+//     IfExpression         condition: expression, consequence: Block, alternative: Block or null
+//     FunctionLiteral      parameters: [Identifier], body: Block
+//     CallExpression       function: expression, arguments: [expression]
+//
+// SCHEMA_VERSION goes up whenever a kind or a field changes meaning or goes
+// away. new kinds and fields may be added without a new version
+pub const SCHEMA_VERSION: i64 = 1;
+
+// the whole document for a source, or the parse errors
+pub fn export(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let program = parse(source)?;
+
+    let document = Json::Object(vec![
+        ("version", Json::Number(SCHEMA_VERSION)),
+        ("tokens", tokens_value(&lex(source))),
+        ("program", program_value(&program)),
+    ]);
+
+    Ok(document.to_string())
+}
+
+pub fn tokens(tokens: &[Token]) -> String {
+    tokens_value(tokens).to_string()
+}
+
+pub fn program(program: &Program) -> String {
+    program_value(program).to_string()
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(x) => write!(f, "{}", x),
+            Json::Number(x) => write!(f, "{}", x),
+            Json::String(x) => write_string(f, x),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+
+    write!(f, "\"")
+}
+
+fn lex(source: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = lexer.next_token();
+        if token.token_type == TokenType::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+fn tokens_value(tokens: &[Token]) -> Json {
+    let tokens = tokens
+        .iter()
+        .map(|x| {
+            Json::Object(vec![
+                ("type", Json::String(format!("{:?}", x.token_type))),
+                ("literal", Json::String(x.literal.clone())),
+                ("span", span(&x.span)),
+            ])
+        })
+        .collect();
+
+    Json::Array(tokens)
+}
+
+fn span(span: &Span) -> Json {
+    Json::Object(vec![
+        ("start", Json::Number(span.start as i64)),
+        ("end", Json::Number(span.end as i64)),
+        ("line", Json::Number(span.line as i64)),
+        ("column", Json::Number(span.column as i64)),
+    ])
+}
+
+fn node(kind: &str, token: &Token, mut fields: Vec<(&'static str, Json)>) -> Json {
+    fields.insert(0, ("kind", Json::String(kind.to_string())));
+    fields.insert(1, ("span", span(&token.span)));
+    Json::Object(fields)
+}
+
+fn program_value(program: &Program) -> Json {
+    Json::Object(vec![
+        ("kind", Json::String("Program".to_string())),
+        ("statements", statements(&program.statements)),
+    ])
+}
+
+fn statements(statements: &[Statements]) -> Json {
+    Json::Array(statements.iter().map(statement).collect())
+}
+
+fn statement(statement: &Statements) -> Json {
+    match statement {
+        Statements::LetStatement(x) => node(
+            "LetStatement",
+            &x.token,
+            vec![
+                ("name", identifier(&x.name)),
+                ("value", expression(&x.value)),
+                ("constant", Json::Bool(x.is_const())),
+            ],
+        ),
+        Statements::AssignStatement(x) => node(
+            "AssignStatement",
+            &x.token,
+            vec![
+                ("name", identifier(&x.name)),
+                ("operator", Json::String(x.operator.clone())),
+                ("value", expression(&x.value)),
+            ],
+        ),
+        Statements::ReturnStatement(x) => node(
+            "ReturnStatement",
+            &x.token,
+            vec![("value", expression(&x.return_value))],
+        ),
+        Statements::ExpressionStatement(x) => node(
+            "ExpressionStatement",
+            &x.token,
+            vec![("expression", expression(&x.expression))],
+        ),
+    }
+}
+
+fn block(block: &BlockStatement) -> Json {
+    node(
+        "Block",
+        &block.token,
+        vec![("statements", statements(&block.statements))],
+    )
+}
+
+fn identifier(identifier: &Identifier) -> Json {
+    node(
+        "Identifier",
+        &identifier.token,
+        vec![("name", Json::String(identifier.value.clone()))],
+    )
+}
+
+fn expression(expression: &Expression) -> Json {
+    match expression {
+        Expression::Identifier(x) => identifier(x),
+        Expression::IntegerLiteral(x) => node(
+            "IntegerLiteral",
+            &x.token,
+            vec![("value", Json::Number(x.value))],
+        ),
+        Expression::BigIntLiteral(x) => node(
+            "BigIntLiteral",
+            &x.token,
+            vec![("value", Json::String(x.value.to_string()))],
+        ),
+        Expression::Boolean(x) => node("Boolean", &x.token, vec![("value", Json::Bool(x.value))]),
+        Expression::NullLiteral(x) => node("NullLiteral", &x.token, vec![]),
+        Expression::PrefixExpression(x) => node(
+            "PrefixExpression",
+            &x.token,
+            vec![
+                ("operator", Json::String(x.operator.clone())),
+                ("right", self::expression(&x.right)),
+            ],
+        ),
+        Expression::InfixExpression(x) => node(
+            "InfixExpression",
+            &x.token,
+            vec![
+                ("operator", Json::String(x.operator.clone())),
+                ("left", self::expression(&x.left)),
+                ("right", self::expression(&x.right)),
+            ],
+        ),
+        Expression::IfExpression(x) => node(
+            "IfExpression",
+            &x.token,
+            vec![
+                ("condition", self::expression(&x.condition)),
+                ("consequence", block(&x.consequence)),
+                (
+                    "alternative",
+                    x.alternative.as_ref().map_or(Json::Null, block),
+                ),
+            ],
+        ),
+        Expression::FunctionLiteral(x) => node(
+            "FunctionLiteral",
+            &x.token,
+            vec![
+                (
+                    "parameters",
+                    Json::Array(x.parameters.iter().map(identifier).collect()),
+                ),
+                ("body", block(&x.body)),
+            ],
+        ),
+        Expression::CallExpression(x) => node(
+            "CallExpression",
+            &x.token,
+            vec![
+                ("function", self::expression(&x.function)),
+                (
+                    "arguments",
+                    Json::Array(x.arguments.iter().map(self::expression).collect()),
+                ),
+            ],
+        ),
+        // parsed programs have none, but the schema stays total
+        Expression::ILLEGAL => Json::Object(vec![("kind", Json::String("Illegal".to_string()))]),
+    }
+}
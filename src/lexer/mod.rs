@@ -1,38 +1,68 @@
 pub mod token;
 
-use self::token::{Token, TokenType::*};
+use self::token::{Span, Token, TokenType::*};
 use super::utils::*;
 use std::string::ToString;
 
 #[derive(Debug)]
 pub struct Lexer {
-    input: String,
+    input: Vec<char>,
     current_position: u32,
     read_position: u32,
     current_ch: char,
+    line: usize,
+    column: usize,
+    trivia: bool,
 }
 
 impl Lexer {
     pub fn new<T: ToString>(input: &T) -> Self {
         let mut lexer = Lexer {
-            input: input.to_string(),
+            input: input.to_string().chars().collect(),
             current_position: 0,
             read_position: 1,
             current_ch: 0 as char,
+            line: 1,
+            column: 0,
+            trivia: false,
         };
 
         lexer.read_char();
         lexer
     }
 
+    // also return whitespace and comments as tokens, so that the source can be
+    // rebuilt from the tokens exactly
+    pub fn with_trivia(mut self) -> Self {
+        self.trivia = true;
+        self
+    }
+
+    pub fn has_trivia(&self) -> bool {
+        self.trivia
+    }
+
     fn get_char(&self, position: u32) -> char {
-        match self.input.chars().nth(position as usize) {
-            Some(x) => x,
+        match self.input.get(position as usize) {
+            Some(x) => *x,
             None => 0 as char,
         }
     }
 
+    // only the end of the buffer ends the input. a NUL inside it is just an
+    // illegal character
+    fn at_end(&self) -> bool {
+        self.current_position as usize > self.input.len()
+    }
+
     fn read_char(&mut self) {
+        if self.current_ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
         self.current_ch = self.get_char(self.current_position);
         self.current_position = self.read_position;
         self.read_position += 1;
@@ -46,7 +76,7 @@ impl Lexer {
         }
 
         let end_position = self.current_position as usize - 1;
-        self.input[start_position..end_position].to_string()
+        self.input[start_position..end_position].iter().collect()
     }
 
     fn read_digit(&mut self) -> String {
@@ -56,62 +86,141 @@ impl Lexer {
         }
 
         let end = self.current_position as usize - 1;
-        self.input[start..end].to_string()
+        self.input[start..end].iter().collect()
     }
 
     fn peek_char(&self) -> char {
-        if self.read_position <= self.input.len() as u32 {
-            return self
-                .input
-                .chars()
-                .nth(self.read_position as usize - 1)
-                .unwrap();
-        };
+        self.get_char(self.read_position - 1)
+    }
 
-        '\0'
+    fn at_trivia(&self) -> bool {
+        match self.current_ch {
+            ' ' | '\n' | '\t' | '\r' => true,
+            '/' => self.peek_char() == '/',
+            _ => false,
+        }
     }
 
+    // comments run from // to the end of the line and are skipped like whitespace
     fn skip_whitespace(&mut self) {
-        while self.current_ch == ' ' || self.current_ch == '\n' || self.current_ch == '\t' {
-            self.read_char();
+        while self.at_trivia() {
+            self.read_trivia();
         }
     }
 
+    fn read_trivia(&mut self) -> Token {
+        let start = self.current_position as usize - 1;
+
+        let token_type = if self.current_ch == '/' {
+            while self.current_ch != '\n' && !self.at_end() {
+                self.read_char();
+            }
+            COMMENT
+        } else {
+            while matches!(self.current_ch, ' ' | '\n' | '\t' | '\r') {
+                self.read_char();
+            }
+            WHITESPACE
+        };
+
+        let end = self.current_position as usize - 1;
+        let literal: String = self.input[start..end].iter().collect();
+        Token::new(token_type, &literal)
+    }
+
     pub fn next_token(&mut self) -> Token {
-        let token: Token;
+        if !self.trivia {
+            self.skip_whitespace();
+        }
 
-        self.skip_whitespace();
+        let start = self.current_position as usize - 1;
+        let line = self.line;
+        let column = self.column;
 
-        match self.current_ch {
+        let mut token = if self.trivia && self.at_trivia() {
+            self.read_trivia()
+        } else {
+            self.read_token()
+        };
+        let end = match token.token_type {
+            EOF => start,
+            _ => self.current_position as usize - 1,
+        };
+
+        token.span = Span {
+            start,
+            end,
+            line,
+            column,
+        };
+        token
+    }
+
+    fn read_token(&mut self) -> Token {
+        let token = match self.current_ch {
+            _ if self.at_end() => Token::new(EOF, &'\0'),
             '=' if self.peek_char() == '=' => {
                 self.read_char();
-                token = Token {
-                    literal: "==".to_string(),
-                    token_type: EQ,
-                };
+                Token::new(EQ, &"==")
             }
             '!' if self.peek_char() == '=' => {
                 self.read_char();
-                token = Token {
-                    literal: "!=".to_string(),
-                    token_type: NOTEQ,
-                };
+                Token::new(NOTEQ, &"!=")
+            }
+            '*' if self.peek_char() == '*' => {
+                self.read_char();
+                Token::new(POWER, &"**")
+            }
+            '+' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(PLUSASSIGN, &"+=")
+            }
+            '-' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(MINUSASSIGN, &"-=")
+            }
+            '*' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(ASTERISKASSIGN, &"*=")
+            }
+            '/' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(SLASHASSIGN, &"/=")
+            }
+            '<' if self.peek_char() == '<' => {
+                self.read_char();
+                Token::new(SHIFTLEFT, &"<<")
+            }
+            '>' if self.peek_char() == '>' => {
+                self.read_char();
+                Token::new(SHIFTRIGHT, &">>")
+            }
+            '<' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(LTEQ, &"<=")
+            }
+            '>' if self.peek_char() == '=' => {
+                self.read_char();
+                Token::new(GTEQ, &">=")
+            }
+            '&' if self.peek_char() == '&' => {
+                self.read_char();
+                Token::new(AND, &"&&")
+            }
+            '|' if self.peek_char() == '|' => {
+                self.read_char();
+                Token::new(OR, &"||")
+            }
+            '?' if self.peek_char() == '?' => {
+                self.read_char();
+                Token::new(NULLISH, &"??")
             }
             ch if is_letter(ch) => {
                 let literal = self.read_identifier();
-                token = Token {
-                    token_type: Token::fron_string(&literal),
-                    literal,
-                };
-                return token;
-            }
-            ch if is_digit(ch) => {
-                return Token {
-                    literal: self.read_digit(),
-                    token_type: INT,
-                }
-            }
-            ch => token = Token::new(Token::from_char(ch), &self.current_ch),
+                return Token::new(Token::fron_string(&literal), &literal);
+            }
+            ch if is_digit(ch) => return Token::new(INT, &self.read_digit()),
+            ch => Token::new(Token::from_char(ch), &ch),
         };
 
         self.read_char();
@@ -1,9 +1,57 @@
 use std::string::ToString;
 
+// every reserved word and the token it lexes to
+pub const KEYWORDS: [(&str, TokenType); 9] = [
+    ("let", TokenType::LET),
+    ("const", TokenType::CONST),
+    ("fn", TokenType::FUNCTION),
+    ("true", TokenType::TRUE),
+    ("false", TokenType::FALSE),
+    ("null", TokenType::NULL),
+    ("if", TokenType::IF),
+    ("else", TokenType::ELSE),
+    ("return", TokenType::RETURN),
+];
+
+// location of a piece of source. offsets count chars, line and column start at 1
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    // the span covering both self and other
+    pub fn to(&self, other: &Span) -> Span {
+        let (first, last) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        Span {
+            start: first.start,
+            end: first.end.max(last.end),
+            line: first.line,
+            column: first.column,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    pub span: Span,
+}
+
+// tokens are equal when they read the same, wherever they were written
+impl PartialEq for Token {
+    fn eq(&self, other: &Token) -> bool {
+        self.token_type == other.token_type && self.literal == other.literal
+    }
 }
 
 impl Token {
@@ -11,6 +59,7 @@ impl Token {
         Token {
             token_type,
             literal: literal.to_string(),
+            span: Span::default(),
         }
     }
 
@@ -20,6 +69,11 @@ impl Token {
             '-' => TokenType::MINUS,
             '/' => TokenType::SLASH,
             '*' => TokenType::ASTERISK,
+            '%' => TokenType::PERCENT,
+            '&' => TokenType::AMPERSAND,
+            '|' => TokenType::PIPE,
+            '^' => TokenType::CARET,
+            '~' => TokenType::TILDE,
             '<' => TokenType::LT,
             '>' => TokenType::GT,
             ',' => TokenType::COMMA,
@@ -30,7 +84,6 @@ impl Token {
             '}' => TokenType::RBRACE,
             '=' => TokenType::ASSIGN,
             '!' => TokenType::BANG,
-            '\0' => TokenType::EOF,
             _ => TokenType::ILLEGAL,
         }
     }
@@ -41,6 +94,14 @@ impl Token {
             "-" => TokenType::MINUS,
             "/" => TokenType::SLASH,
             "*" => TokenType::ASTERISK,
+            "%" => TokenType::PERCENT,
+            "**" => TokenType::POWER,
+            "&" => TokenType::AMPERSAND,
+            "|" => TokenType::PIPE,
+            "^" => TokenType::CARET,
+            "~" => TokenType::TILDE,
+            "<<" => TokenType::SHIFTLEFT,
+            ">>" => TokenType::SHIFTRIGHT,
             "<" => TokenType::LT,
             ">" => TokenType::GT,
             "," => TokenType::COMMA,
@@ -50,41 +111,69 @@ impl Token {
             "{" => TokenType::LBRACE,
             "}" => TokenType::RBRACE,
             "=" => TokenType::ASSIGN,
+            "+=" => TokenType::PLUSASSIGN,
+            "-=" => TokenType::MINUSASSIGN,
+            "*=" => TokenType::ASTERISKASSIGN,
+            "/=" => TokenType::SLASHASSIGN,
             "!" => TokenType::BANG,
             "==" => TokenType::EQ,
             "!=" => TokenType::NOTEQ,
-            "let" => TokenType::LET,
-            "fn" => TokenType::FUNCTION,
-            "true" => TokenType::TRUE,
-            "false" => TokenType::FALSE,
-            "if" => TokenType::IF,
-            "else" => TokenType::ELSE,
-            "return" => TokenType::RETURN,
+            "<=" => TokenType::LTEQ,
+            ">=" => TokenType::GTEQ,
+            "&&" => TokenType::AND,
+            "||" => TokenType::OR,
+            "??" => TokenType::NULLISH,
             "" => TokenType::EOF,
-            _ => TokenType::IDENT,
+            _ => KEYWORDS
+                .iter()
+                .find(|&&(keyword, _)| keyword == s)
+                .map_or(TokenType::IDENT, |(_, token_type)| token_type.clone()),
         }
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Default)]
 pub enum TokenType {
+    #[default]
     ILLEGAL,
     EOF,
 
+    // only produced by a lexer made with Lexer::with_trivia
+    WHITESPACE,
+    COMMENT,
+
     IDENT,
     INT,
 
     ASSIGN,
+    PLUSASSIGN,
+    MINUSASSIGN,
+    ASTERISKASSIGN,
+    SLASHASSIGN,
     PLUS,
     MINUS,
     BANG,
     ASTERISK,
     SLASH,
+    PERCENT,
+    POWER,
+    AMPERSAND,
+    PIPE,
+    CARET,
+    TILDE,
+    SHIFTLEFT,
+    SHIFTRIGHT,
     EQ,
     NOTEQ,
 
     LT,
     GT,
+    LTEQ,
+    GTEQ,
+
+    AND,
+    OR,
+    NULLISH,
 
     COMMA,
     SEMICOLON,
@@ -96,15 +185,11 @@ pub enum TokenType {
 
     FUNCTION,
     LET,
+    CONST,
     TRUE,
     FALSE,
+    NULL,
     IF,
     ELSE,
     RETURN,
 }
-
-impl Default for TokenType {
-    fn default() -> Self {
-        TokenType::ILLEGAL
-    }
-}
@@ -1,6 +1,15 @@
+pub mod cli;
+pub mod diagnostics;
+pub mod dot;
 pub mod evaluator;
+pub mod formatter;
+pub mod interrupt;
+pub mod json;
 pub mod lexer;
+pub mod lint;
 pub mod object;
+pub mod optimizer;
 pub mod parser;
 pub mod repl;
+pub mod resolver;
 pub mod utils;
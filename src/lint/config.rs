@@ -0,0 +1,216 @@
+use super::LINTS;
+use crate::diagnostics::Diagnostic;
+use crate::lexer::token::Span;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE_NAME: &str = "lolo.toml";
+
+const INVALID_DIRECTIVE: &str = "invalid_directive";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Level {
+    pub fn parse(name: &str) -> Option<Level> {
+        match name {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Allow => write!(f, "allow"),
+            Level::Warn => write!(f, "warn"),
+            Level::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+// lint levels from the [lints] table of a lolo.toml, every lint warns by default
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    levels: HashMap<String, Level>,
+}
+
+impl Config {
+    // only the small part of toml we need: comments, a [lints] table and
+    // `name = "level"` pairs
+    pub fn parse(source: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut in_lints = false;
+
+        for (i, line) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let line = match line.find('#') {
+                Some(x) => &line[..x],
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                if !line.ends_with(']') {
+                    return Err(format!("line {}: unclosed table header", line_number));
+                }
+                in_lints = line[1..line.len() - 1].trim() == "lints";
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some(x) => x,
+                None => return Err(format!("line {}: expected `name = \"level\"`", line_number)),
+            };
+
+            // keys outside [lints] belong to other tools
+            if !in_lints {
+                continue;
+            }
+
+            let key = key.trim();
+            let value = value.trim();
+            if !(value.len() >= 2 && value.starts_with('"') && value.ends_with('"')) {
+                return Err(format!(
+                    "line {}: the level of `{}` must be a quoted string",
+                    line_number, key
+                ));
+            }
+
+            let level = match Level::parse(&value[1..value.len() - 1]) {
+                Some(x) => x,
+                None => {
+                    return Err(format!(
+                        "line {}: unknown level {}, expected \"allow\", \"warn\" or \"deny\"",
+                        line_number, value
+                    ))
+                }
+            };
+
+            if !LINTS.contains(&key) {
+                return Err(format!("line {}: unknown lint `{}`", line_number, key));
+            }
+
+            config.set(key, level);
+        }
+
+        Ok(config)
+    }
+
+    // the nearest lolo.toml in the directory of path or any of its parents
+    pub fn find(path: &Path) -> Option<PathBuf> {
+        let path = path.canonicalize().ok()?;
+        let start = if path.is_dir() { &path } else { path.parent()? };
+
+        start
+            .ancestors()
+            .map(|x| x.join(CONFIG_FILE_NAME))
+            .find(|x| x.is_file())
+    }
+
+    pub fn set(&mut self, lint: &str, level: Level) {
+        self.levels.insert(lint.to_string(), level);
+    }
+
+    pub fn level(&self, lint: &str) -> Level {
+        self.levels.get(lint).cloned().unwrap_or(Level::Warn)
+    }
+}
+
+// a `// lolo: allow(name, ...)` comment. it applies to its own line when it
+// follows code, and to the next line of code when it stands alone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    pub line: usize,
+    pub level: Level,
+    pub lints: Vec<String>,
+}
+
+// malformed directives are reported as warnings and otherwise ignored
+pub fn parse_directives(source: &str) -> (Vec<Directive>, Vec<Diagnostic>) {
+    let mut directives = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut waiting: Vec<Directive> = Vec::new();
+    let mut offset = 0;
+
+    for (i, line) in source.split('\n').enumerate() {
+        let line_number = i + 1;
+        let line_length = line.chars().count();
+        let comment = line.find("//");
+        let code = match comment {
+            Some(x) => &line[..x],
+            None => line,
+        };
+        let has_code = !code.trim().is_empty();
+
+        if has_code {
+            for mut directive in waiting.drain(..) {
+                directive.line = line_number;
+                directives.push(directive);
+            }
+        }
+
+        if let Some(x) = comment {
+            let column = line[..x].chars().count();
+            let span = Span {
+                start: offset + column,
+                end: offset + line_length,
+                line: line_number,
+                column: column + 1,
+            };
+
+            match parse_directive(&line[x + 2..], line_number) {
+                Ok(Some(directive)) if has_code => directives.push(directive),
+                Ok(Some(directive)) => waiting.push(directive),
+                Ok(None) => (),
+                Err(message) => diagnostics.push(
+                    Diagnostic::warning(INVALID_DIRECTIVE, message)
+                        .with_primary(span, "this directive is ignored"),
+                ),
+            }
+        }
+
+        offset += line_length + 1;
+    }
+
+    (directives, diagnostics)
+}
+
+fn parse_directive(comment: &str, line: usize) -> Result<Option<Directive>, String> {
+    let rest = match comment.trim().strip_prefix("lolo:") {
+        Some(x) => x.trim(),
+        None => return Ok(None),
+    };
+
+    let malformed = || "malformed lint directive, expected `lolo: allow(name, ...)`".to_string();
+
+    let open = rest.find('(').ok_or_else(malformed)?;
+    if !rest.ends_with(')') {
+        return Err(malformed());
+    }
+
+    let level = Level::parse(rest[..open].trim()).ok_or_else(malformed)?;
+    let mut lints = Vec::new();
+
+    for name in rest[open + 1..rest.len() - 1].split(',') {
+        let name = name.trim();
+        if !LINTS.contains(&name) {
+            return Err(format!("unknown lint `{}`", name));
+        }
+        lints.push(name.to_string());
+    }
+
+    Ok(Some(Directive { line, level, lints }))
+}
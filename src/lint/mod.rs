@@ -0,0 +1,288 @@
+pub mod config;
+
+pub use self::config::{Config, Level};
+
+use self::config::{parse_directives, Directive};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lexer::token::Span;
+use crate::parser::ast::expressions::*;
+use crate::parser::ast::statements::*;
+use crate::parser::ast::visit::*;
+use crate::parser::ast::Program;
+
+pub const UNUSED_VARIABLE: &str = "unused_variable";
+pub const UNUSED_PARAMETER: &str = "unused_parameter";
+pub const SHADOWED_NAME: &str = "shadowed_name";
+pub const UNREACHABLE_CODE: &str = "unreachable_code";
+pub const CONSTANT_CONDITION: &str = "constant_condition";
+pub const SELF_COMPARISON: &str = "self_comparison";
+
+pub const LINTS: [&str; 6] = [
+    UNUSED_VARIABLE,
+    UNUSED_PARAMETER,
+    SHADOWED_NAME,
+    UNREACHABLE_CODE,
+    CONSTANT_CONDITION,
+    SELF_COMPARISON,
+];
+
+// warns about code that runs but is probably not what was meant. each
+// diagnostic's code is the name of its lint, and its severity follows the
+// level set by the config or a `// lolo: allow(...)` comment
+pub fn lint(program: &Program, source: &str, config: &Config) -> Vec<Diagnostic> {
+    let mut linter = Linter::default();
+
+    linter.begin_scope();
+    linter.visit_program(program);
+    linter.end_scope();
+
+    let (directives, mut diagnostics) = parse_directives(source);
+
+    for mut diagnostic in linter.diagnostics {
+        let line = diagnostic.primary.as_ref().map_or(0, |x| x.span.line);
+
+        match level(config, &directives, &diagnostic.code, line) {
+            Level::Allow => continue,
+            Level::Warn => diagnostic.severity = Severity::Warning,
+            Level::Deny => diagnostic.severity = Severity::Error,
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    diagnostics.sort_by_key(|x| x.primary.as_ref().map(|label| label.span.start));
+    diagnostics
+}
+
+// a directive on the line wins over the config, and later directives over earlier ones
+fn level(config: &Config, directives: &[Directive], lint: &str, line: usize) -> Level {
+    directives
+        .iter()
+        .rev()
+        .find(|x| x.line == line && x.lints.iter().any(|name| name == lint))
+        .map_or_else(|| config.level(lint), |x| x.level)
+}
+
+struct Binding {
+    name: String,
+    span: Span,
+    is_parameter: bool,
+    used: bool,
+}
+
+#[derive(Default)]
+struct Scope {
+    bindings: Vec<Binding>,
+    // as in the resolver, bodies wait until every name in the scope is bound
+    pending: Vec<FunctionLiteral>,
+}
+
+#[derive(Default)]
+struct Linter {
+    scopes: Vec<Scope>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Linter {
+    fn begin_scope(&mut self) {
+        self.scopes.push(Scope::default());
+    }
+
+    fn end_scope(&mut self) {
+        let pending = std::mem::take(&mut self.current_scope().pending);
+        for function in &pending {
+            self.lint_function_body(function);
+        }
+
+        let scope = self.scopes.pop().expect("linter has no scope");
+        for binding in scope.bindings.iter().filter(|x| !x.used) {
+            let diagnostic = if binding.is_parameter {
+                Diagnostic::warning(
+                    UNUSED_PARAMETER,
+                    format!("unused parameter: `{}`", binding.name),
+                )
+                .with_primary(binding.span, "never used in the function body")
+            } else {
+                Diagnostic::warning(
+                    UNUSED_VARIABLE,
+                    format!("unused variable: `{}`", binding.name),
+                )
+                .with_primary(binding.span, "never used")
+            };
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn current_scope(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("linter has no scope")
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.bindings.iter_mut().rev())
+            .find(|x| x.name == name)
+    }
+
+    fn bind(&mut self, identifier: &Identifier, is_parameter: bool) {
+        self.current_scope().bindings.push(Binding {
+            name: identifier.value.clone(),
+            span: identifier.token.span,
+            is_parameter,
+            used: false,
+        });
+    }
+
+    fn lint_function_body(&mut self, function: &FunctionLiteral) {
+        self.begin_scope();
+
+        for parameter in &function.parameters {
+            self.check_shadowing(parameter);
+            self.bind(parameter, true);
+        }
+
+        self.visit_block(&function.body);
+        self.end_scope();
+    }
+
+    fn check_unreachable(&mut self, statements: &[Statements]) {
+        let mut returned: Option<Span> = None;
+
+        for statement in statements {
+            if let Some(return_span) = returned.take() {
+                let diagnostic = Diagnostic::warning(UNREACHABLE_CODE, "unreachable statement")
+                    .with_primary(statement_span(statement), "unreachable statement")
+                    .with_secondary(
+                        return_span,
+                        "any code following this `return` is unreachable",
+                    );
+                self.diagnostics.push(diagnostic);
+            }
+
+            if let Statements::ReturnStatement(x) = statement {
+                returned = Some(x.token.span);
+            }
+        }
+    }
+
+    fn check_shadowing(&mut self, name: &Identifier) {
+        let previous = match self.lookup(&name.value) {
+            Some(x) => x.span,
+            None => return,
+        };
+
+        let diagnostic = Diagnostic::warning(
+            SHADOWED_NAME,
+            format!("`{}` shadows an earlier binding", name.value),
+        )
+        .with_primary(name.token.span, "shadows the earlier binding")
+        .with_secondary(previous, format!("`{}` first bound here", name.value));
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn check_self_comparison(&mut self, infix: &InfixExpression) {
+        let always = match infix.operator.as_str() {
+            "==" | "<=" | ">=" => true,
+            "!=" | "<" | ">" => false,
+            _ => return,
+        };
+
+        if !is_pure(&infix.left) || infix.left.to_string() != infix.right.to_string() {
+            return;
+        }
+
+        let diagnostic = Diagnostic::warning(
+            SELF_COMPARISON,
+            format!("`{}` is compared with itself", infix.left),
+        )
+        .with_primary(
+            infix.left.span().to(&infix.right.span()),
+            format!("this is always {}", always),
+        );
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Visitor for Linter {
+    fn visit_program(&mut self, program: &Program) {
+        self.check_unreachable(&program.statements);
+        walk_program(self, program);
+    }
+
+    fn visit_block(&mut self, block: &BlockStatement) {
+        self.check_unreachable(&block.statements);
+        walk_block(self, block);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        if let Some(binding) = self.lookup(&identifier.value) {
+            binding.used = true;
+        }
+    }
+
+    // the target of an assignment is written, not read, so it does not count as a use
+    fn visit_assign_statement(&mut self, statement: &AssignStatement) {
+        self.visit_expression(&statement.value);
+    }
+
+    // parameters are bound by lint_function_body, so this only sees let names
+    fn visit_binding(&mut self, identifier: &Identifier) {
+        self.check_shadowing(identifier);
+        self.bind(identifier, false);
+    }
+
+    fn visit_infix_expression(&mut self, expression: &InfixExpression) {
+        self.check_self_comparison(expression);
+        walk_infix_expression(self, expression);
+    }
+
+    fn visit_if_expression(&mut self, expression: &IfExpression) {
+        if is_constant(&expression.condition) {
+            let diagnostic = Diagnostic::warning(CONSTANT_CONDITION, "constant `if` condition")
+                .with_primary(expression.condition.span(), "this condition never changes");
+            self.diagnostics.push(diagnostic);
+        }
+
+        walk_if_expression(self, expression);
+    }
+
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        self.current_scope().pending.push(function.clone());
+    }
+}
+
+fn statement_span(statement: &Statements) -> Span {
+    match statement {
+        Statements::LetStatement(x) => x.token.span.to(&x.value.span()),
+        Statements::AssignStatement(x) => x.name.token.span.to(&x.value.span()),
+        Statements::ReturnStatement(x) => x.token.span.to(&x.return_value.span()),
+        Statements::ExpressionStatement(x) => x.expression.span(),
+    }
+}
+
+// literals and operators on literals, which evaluate the same way every time
+fn is_constant(expression: &Expression) -> bool {
+    match expression {
+        Expression::IntegerLiteral(_)
+        | Expression::BigIntLiteral(_)
+        | Expression::Boolean(_)
+        | Expression::NullLiteral(_) => true,
+        Expression::PrefixExpression(x) => is_constant(&x.right),
+        Expression::InfixExpression(x) => is_constant(&x.left) && is_constant(&x.right),
+        _ => false,
+    }
+}
+
+// expressions without calls, so evaluating them twice gives the same value
+fn is_pure(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(_)
+        | Expression::IntegerLiteral(_)
+        | Expression::BigIntLiteral(_)
+        | Expression::Boolean(_)
+        | Expression::NullLiteral(_) => true,
+        Expression::PrefixExpression(x) => is_pure(&x.right),
+        Expression::InfixExpression(x) => is_pure(&x.left) && is_pure(&x.right),
+        _ => false,
+    }
+}
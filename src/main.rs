@@ -1,5 +1,18 @@
 extern crate lolo;
 
-fn main() {
-    lolo::repl::run().expect("faild");
+use std::env;
+use std::process::ExitCode;
+use std::thread;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    // the evaluator recurses on the native stack, which the main thread may not
+    // have enough of
+    thread::Builder::new()
+        .stack_size(lolo::evaluator::STACK_SIZE)
+        .spawn(move || lolo::cli::run(&args))
+        .expect("failed to start the interpreter thread")
+        .join()
+        .unwrap_or(ExitCode::FAILURE)
 }
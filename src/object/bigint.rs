@@ -0,0 +1,319 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// each limb holds nine decimal digits, so parsing and printing need no division
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+// an integer of any size. the limbs hold the magnitude, least significant
+// first and without trailing zeros, so zero has no limbs and is never negative
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn from_i64(n: i64) -> BigInt {
+        let mut magnitude = n.unsigned_abs();
+        let mut limbs = Vec::new();
+
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+
+        BigInt::new(n < 0, limbs)
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let mut magnitude: i128 = 0;
+
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude.checked_mul(BASE as i128)? + *limb as i128;
+            if magnitude > i64::MAX as i128 + 1 {
+                return None;
+            }
+        }
+
+        let value = if self.negative { -magnitude } else { magnitude };
+        i64::try_from(value).ok()
+    }
+
+    // decimal digits with an optional leading minus
+    pub fn parse(s: &str) -> Option<BigInt> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|x| x.is_ascii_digit()) {
+            return None;
+        }
+
+        let limbs = digits
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, digit| limb * 10 + (digit - b'0') as u32)
+            })
+            .collect();
+
+        Some(BigInt::new(negative, limbs))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // the number of decimal digits, not counting the sign
+    pub fn digits(&self) -> usize {
+        match self.limbs.last() {
+            Some(last) => (self.limbs.len() - 1) * BASE_DIGITS + last.to_string().len(),
+            None => 1,
+        }
+    }
+
+    // quotient and remainder rounded toward zero, as for i64, or None for
+    // a zero divisor
+    pub fn div_rem(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &divisor.limbs);
+        Some((
+            BigInt::new(self.negative != divisor.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        ))
+    }
+
+    // None when the result has more than max_digits digits, which is found
+    // out before any number much larger than that is computed
+    pub fn checked_pow(&self, mut exponent: u64, max_digits: usize) -> Option<BigInt> {
+        let mut result = BigInt::from_i64(1);
+        let mut base = self.clone();
+
+        loop {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+                if result.digits() > max_digits {
+                    return None;
+                }
+            }
+
+            exponent >>= 1;
+            if exponent == 0 {
+                return Some(result);
+            }
+
+            // the base only grows, except for 0, 1 and -1 whose powers are small
+            base = &base * &base;
+            if base.digits() > max_digits {
+                return None;
+            }
+        }
+    }
+
+    fn new(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut limbs = self.limbs.iter().rev();
+
+        match limbs.next() {
+            Some(first) if self.negative => write!(f, "-{}", first)?,
+            Some(first) => write!(f, "{}", first)?,
+            None => return write!(f, "0"),
+        }
+
+        for limb in limbs {
+            write!(f, "{:09}", limb)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.limbs, &other.limbs),
+            (true, true) => compare_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+
+        // the sign of the larger magnitude wins
+        match compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::new(other.negative, sub_magnitude(&other.limbs, &self.limbs)),
+            _ => BigInt::new(self.negative, sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut product = vec![0u64; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let x = product[i + j] + *a as u64 * *b as u64 + carry;
+                product[i + j] = x % BASE;
+                carry = x / BASE;
+            }
+            product[i + other.limbs.len()] += carry;
+        }
+
+        let limbs = product.into_iter().map(|x| x as u32).collect();
+        BigInt::new(self.negative != other.negative, limbs)
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+
+    for i in 0..a.len().max(b.len()) {
+        let x = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push((x % BASE) as u32);
+        carry = x / BASE;
+    }
+
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+// a - b, for a at least as large as b
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+
+    for (i, x) in a.iter().enumerate() {
+        let y = *b.get(i).unwrap_or(&0) as i64 + borrow;
+        let mut x = *x as i64 - y;
+
+        borrow = 0;
+        if x < 0 {
+            x += BASE as i64;
+            borrow = 1;
+        }
+        difference.push(x as u32);
+    }
+
+    difference
+}
+
+fn mul_small(a: &[u32], n: u64) -> Vec<u32> {
+    let mut product = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+
+    for x in a {
+        let x = *x as u64 * n + carry;
+        product.push((x % BASE) as u32);
+        carry = x / BASE;
+    }
+
+    if carry > 0 {
+        product.push(carry as u32);
+    }
+    product
+}
+
+fn trim(mut limbs: Vec<u32>) -> Vec<u32> {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    limbs
+}
+
+// schoolbook long division, one limb of the quotient at a time. each limb is
+// found by binary search, which is slow for huge numbers but simple
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+
+    for i in (0..a.len()).rev() {
+        remainder.insert(0, a[i]);
+        remainder = trim(remainder);
+
+        let (mut low, mut high) = (0, BASE - 1);
+        while low < high {
+            let middle = (low + high).div_ceil(2);
+            if compare_magnitude(&trim(mul_small(b, middle)), &remainder) == Ordering::Greater {
+                high = middle - 1;
+            } else {
+                low = middle;
+            }
+        }
+
+        if low > 0 {
+            remainder = trim(sub_magnitude(&remainder, &trim(mul_small(b, low))));
+        }
+        quotient[i] = low as u32;
+    }
+
+    (trim(quotient), remainder)
+}
@@ -0,0 +1,108 @@
+use super::Object;
+use crate::lexer::token::Span;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Default)]
+pub struct Environment {
+    store: HashMap<String, Object>,
+    // where each constant of this scope was declared
+    constants: HashMap<String, Span>,
+    outer: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    pub fn new() -> Rc<RefCell<Environment>> {
+        Rc::new(RefCell::new(Environment::default()))
+    }
+
+    pub fn new_enclosed(outer: &Rc<RefCell<Environment>>) -> Rc<RefCell<Environment>> {
+        Rc::new(RefCell::new(Environment {
+            outer: Some(Rc::clone(outer)),
+            ..Environment::default()
+        }))
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        match self.store.get(name) {
+            Some(x) => Some(x.clone()),
+            None => self
+                .outer
+                .as_ref()
+                .and_then(|outer| outer.borrow().get(name)),
+        }
+    }
+
+    pub fn set(&mut self, name: String, value: Object) {
+        self.store.insert(name, value);
+    }
+
+    // binds a name that cannot be assigned to, nor bound again in this scope
+    pub fn set_constant(&mut self, name: String, value: Object, declaration: Span) {
+        self.constants.insert(name.clone(), declaration);
+        self.set(name, value);
+    }
+
+    // where the name was declared, if the innermost binding of it is a constant
+    pub fn constant(&self, name: &str) -> Option<Span> {
+        if self.store.contains_key(name) {
+            return self.local_constant(name);
+        }
+
+        self.outer
+            .as_ref()
+            .and_then(|outer| outer.borrow().constant(name))
+    }
+
+    // like constant, but only looks in this scope
+    pub fn local_constant(&self, name: &str) -> Option<Span> {
+        self.constants.get(name).cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.store.contains_key(name)
+            || self
+                .outer
+                .as_ref()
+                .is_some_and(|outer| outer.borrow().contains(name))
+    }
+
+    // rebinds the name in the innermost scope that has it, so a closure can
+    // change a variable of the function it was made in. a name no scope has
+    // is bound in this one, as by set
+    pub fn assign(&mut self, name: &str, value: Object) {
+        if self.store.contains_key(name) {
+            self.store.insert(name.to_string(), value);
+            return;
+        }
+
+        match &self.outer {
+            Some(outer) if outer.borrow().contains(name) => outer.borrow_mut().assign(name, value),
+            _ => self.set(name.to_string(), value),
+        }
+    }
+
+    // names visible from this scope, including the outer ones
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.store.keys().cloned().collect();
+
+        if let Some(outer) = &self.outer {
+            names.extend(outer.borrow().names());
+        }
+
+        names
+    }
+
+    // bindings of this scope only, sorted by name
+    pub fn bindings(&self) -> Vec<(String, Object)> {
+        let mut bindings: Vec<(String, Object)> = self
+            .store
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
+    }
+}
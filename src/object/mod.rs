@@ -1,26 +1,93 @@
+pub mod bigint;
+pub mod environment;
+
+pub use self::bigint::BigInt;
+pub use self::environment::Environment;
 pub use self::ObjectType::*;
+use crate::lexer::token::Span;
+use crate::parser::ast::expressions::{BlockStatement, Identifier};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ObjectType {
     Integer(i64),
+    // only for values that do not fit in an Integer
+    BigInt(BigInt),
     Boolean(bool),
+    // shared, as a function is passed around far more often than changed
+    Function(Rc<Function>),
+    ReturnValue(Box<Object>),
     Null,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Object {
     pub object_type: ObjectType,
 }
 
+#[derive(Clone)]
+pub struct Function {
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+    pub env: Rc<RefCell<Environment>>,
+    // where the function literal was written
+    pub span: Span,
+    // the name of the first let binding the function was assigned to
+    pub name: Option<String>,
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parameters: Vec<&str> = self.parameters.iter().map(|p| p.value.as_str()).collect();
+
+        write!(f, "fn({}) {{ {} }}", parameters.join(", "), self.body)
+    }
+}
+
+// the environment may hold the function itself, so it is left out to avoid endless recursion
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Function({})", self)
+    }
+}
+
+// the same code closed over the same environment. the environment is compared by
+// pointer, as it may hold the function itself
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.parameters == other.parameters
+            && self.body == other.body
+    }
+}
+
+impl Eq for Function {}
+
 impl Object {
     pub fn inspect(&self) -> String {
         match self.object_type {
             Integer(x) => x.to_string(),
+            ObjectType::BigInt(ref x) => x.to_string(),
             Boolean(x) => x.to_string(),
+            ObjectType::Function(ref x) => x.to_string(),
+            ReturnValue(ref x) => x.inspect(),
             Null => "null".to_string(),
         }
     }
 
+    pub fn type_name(&self) -> &'static str {
+        match self.object_type {
+            Integer(_) => "INTEGER",
+            ObjectType::BigInt(_) => "BIGINT",
+            Boolean(_) => "BOOLEAN",
+            ObjectType::Function(_) => "FUNCTION",
+            ReturnValue(ref x) => x.type_name(),
+            Null => "NULL",
+        }
+    }
+
     // FIXME fron_int, from_boolは一般化できそうな雰囲気isある
     pub fn from_int(integer: i64) -> Object {
         Object {
@@ -28,6 +95,16 @@ impl Object {
         }
     }
 
+    // an Integer when the value fits, so each value has a single representation
+    pub fn from_big_int(integer: BigInt) -> Object {
+        let object_type = match integer.to_i64() {
+            Some(x) => Integer(x),
+            None => ObjectType::BigInt(integer),
+        };
+
+        Object { object_type }
+    }
+
     pub fn from_bool(boolean: bool) -> Object {
         Object {
             object_type: Boolean(boolean),
@@ -41,6 +118,15 @@ impl Object {
         }
     }
 
+    // any integer, whichever way it is stored
+    pub fn big_int_value(&self) -> Option<BigInt> {
+        match self.object_type {
+            Integer(x) => Some(BigInt::from_i64(x)),
+            ObjectType::BigInt(ref x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+
     pub fn boolean_value(&self) -> bool {
         match self.object_type {
             Boolean(x) => x,
@@ -48,19 +134,24 @@ impl Object {
         }
     }
 
+    // the equality of == and !=, defined for any two values. values of
+    // different types are never equal, so `1 == true` is false and
+    // `null == null` is true. integers are equal by value whichever way they
+    // are stored, and functions when they have the same code and closure
+    pub fn equals(&self, other: &Object) -> bool {
+        self.object_type == other.object_type
+    }
+
     pub fn is_int(&self) -> bool {
-        match self.object_type {
-            Integer(_) => true,
-            _ => false,
-        }
+        matches!(self.object_type, Integer(_))
+    }
+
+    pub fn is_return_value(&self) -> bool {
+        matches!(self.object_type, ReturnValue(_))
     }
 
     pub fn is_truthy(&self) -> bool {
-        match self.object_type {
-            Null => false,
-            Boolean(false) => false,
-            _ => true,
-        }
+        !matches!(self.object_type, Null | Boolean(false))
     }
 }
 
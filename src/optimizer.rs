@@ -0,0 +1,258 @@
+use crate::lexer::token::{Span, Token, TokenType};
+use crate::parser::ast::expressions::*;
+use crate::parser::ast::fold::*;
+use crate::parser::ast::statements::*;
+use crate::parser::ast::Program;
+use std::convert::TryFrom;
+
+// simplifies a program without changing what it does: operators on literals
+// are computed, ifs with a literal condition keep only the branch they take
+// and statements after a return are dropped. anything that would fail at
+// runtime, like `1 / 0` or `-true`, is left for the evaluator to report
+pub fn optimize(program: Program) -> Program {
+    Optimizer.fold_program(program)
+}
+
+struct Optimizer;
+
+impl Fold for Optimizer {
+    fn fold_program(&mut self, program: Program) -> Program {
+        Program {
+            statements: self.statements(program.statements),
+        }
+    }
+
+    fn fold_block(&mut self, block: BlockStatement) -> BlockStatement {
+        BlockStatement {
+            token: block.token,
+            statements: self.statements(block.statements),
+        }
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        let expression = walk_expression(self, expression);
+
+        let folded = match &expression {
+            Expression::PrefixExpression(x) => fold_prefix(x),
+            Expression::InfixExpression(x) => fold_infix(x),
+            Expression::IfExpression(x) => match taken_branch(x) {
+                Some(Some(block)) => only_expression(block),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        folded.unwrap_or(expression)
+    }
+}
+
+impl Optimizer {
+    // an if statement is replaced by the statements of the branch it takes,
+    // which is safe as blocks do not open a scope of their own
+    fn statements(&mut self, statements: Vec<Statements>) -> Vec<Statements> {
+        let last = statements.len().saturating_sub(1);
+        let mut folded = Vec::new();
+
+        for (i, statement) in statements.into_iter().enumerate() {
+            let statement = self.fold_statement(statement);
+
+            let if_expression = match &statement {
+                Statements::ExpressionStatement(ExpressionStatement {
+                    expression: Expression::IfExpression(x),
+                    ..
+                }) => x,
+                _ => {
+                    folded.push(statement);
+                    continue;
+                }
+            };
+
+            match taken_branch(if_expression) {
+                Some(Some(block)) if !block.statements.is_empty() => {
+                    folded.extend(block.statements.iter().cloned())
+                }
+                // the value of the last statement is the value of the block,
+                // so an if that gives nothing stays to give it
+                Some(_) if i == last => {
+                    folded.push(Statements::ExpressionStatement(ExpressionStatement {
+                        token: if_expression.token.clone(),
+                        expression: Expression::IfExpression(IfExpression {
+                            token: if_expression.token.clone(),
+                            condition: if_expression.condition.clone(),
+                            consequence: BlockStatement {
+                                token: if_expression.consequence.token.clone(),
+                                statements: Vec::new(),
+                            },
+                            alternative: None,
+                        }),
+                    }))
+                }
+                Some(_) => (),
+                None => folded.push(statement),
+            }
+        }
+
+        if let Some(i) = folded
+            .iter()
+            .position(|x| matches!(x, Statements::ReturnStatement(_)))
+        {
+            folded.truncate(i + 1);
+        }
+
+        folded
+    }
+}
+
+// None when the condition is not a literal, otherwise the branch taken, if any
+fn taken_branch(if_expression: &IfExpression) -> Option<Option<&BlockStatement>> {
+    if truthiness(&if_expression.condition)? {
+        Some(Some(&if_expression.consequence))
+    } else {
+        Some(if_expression.alternative.as_ref())
+    }
+}
+
+fn only_expression(block: &BlockStatement) -> Option<Expression> {
+    match block.statements.as_slice() {
+        [Statements::ExpressionStatement(x)] => Some(x.expression.clone()),
+        _ => None,
+    }
+}
+
+fn fold_prefix(prefix: &PrefixExpression) -> Option<Expression> {
+    let span = Expression::PrefixExpression(prefix.clone()).span();
+
+    match prefix.operator.as_str() {
+        "!" => Some(boolean_literal(!truthiness(&prefix.right)?, span)),
+        "-" => integer_literal(integer(&prefix.right)?.checked_neg()?, span),
+        "~" => integer_literal(!integer(&prefix.right)?, span),
+        _ => None,
+    }
+}
+
+fn fold_infix(infix: &InfixExpression) -> Option<Expression> {
+    let span = infix.left.span().to(&infix.right.span());
+
+    // a literal on the left may decide the result before the right runs
+    if infix.operator == "??" {
+        return match infix.left.as_ref() {
+            Expression::NullLiteral(_) => Some(*infix.right.clone()),
+            left if truthiness(left).is_some() => Some(left.clone()),
+            _ => None,
+        };
+    }
+
+    if let "&&" | "||" = infix.operator.as_str() {
+        let left = truthiness(&infix.left)?;
+
+        return match (infix.operator.as_str(), left) {
+            ("&&", false) => Some(boolean_literal(false, span)),
+            ("||", true) => Some(boolean_literal(true, span)),
+            _ => Some(boolean_literal(truthiness(&infix.right)?, span)),
+        };
+    }
+
+    if let (Some(left), Some(right)) = (integer(&infix.left), integer(&infix.right)) {
+        return match infix.operator.as_str() {
+            "+" => integer_literal(left.checked_add(right)?, span),
+            "-" => integer_literal(left.checked_sub(right)?, span),
+            "*" => integer_literal(left.checked_mul(right)?, span),
+            "/" => integer_literal(left.checked_div(right)?, span),
+            "%" => integer_literal(left.checked_rem(right)?, span),
+            "**" => integer_literal(left.checked_pow(u32::try_from(right).ok()?)?, span),
+            "&" => integer_literal(left & right, span),
+            "|" => integer_literal(left | right, span),
+            "^" => integer_literal(left ^ right, span),
+            "<<" if right >= 64 => integer_literal(0, span),
+            "<<" if right >= 0 => integer_literal(left << right, span),
+            ">>" if right >= 0 => integer_literal(left >> right.min(63), span),
+            "<" => Some(boolean_literal(left < right, span)),
+            ">" => Some(boolean_literal(left > right, span)),
+            "<=" => Some(boolean_literal(left <= right, span)),
+            ">=" => Some(boolean_literal(left >= right, span)),
+            "==" => Some(boolean_literal(left == right, span)),
+            "!=" => Some(boolean_literal(left != right, span)),
+            _ => None,
+        };
+    }
+
+    let (left, right) = (boolean(&infix.left)?, boolean(&infix.right)?);
+    match infix.operator.as_str() {
+        "==" => Some(boolean_literal(left == right, span)),
+        "!=" => Some(boolean_literal(left != right, span)),
+        _ => None,
+    }
+}
+
+// the value of an integer literal, or of one with a minus in front
+fn integer(expression: &Expression) -> Option<i64> {
+    match expression {
+        Expression::IntegerLiteral(x) => Some(x.value),
+        Expression::PrefixExpression(x) if x.operator == "-" => match x.right.as_ref() {
+            Expression::IntegerLiteral(x) => x.value.checked_neg(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// whether a literal counts as true, as a condition or an operand of && and ||
+fn truthiness(expression: &Expression) -> Option<bool> {
+    match expression {
+        Expression::NullLiteral(_) => Some(false),
+        _ => boolean(expression).or_else(|| integer(expression).map(|_| true)),
+    }
+}
+
+fn boolean(expression: &Expression) -> Option<bool> {
+    match expression {
+        Expression::Boolean(x) => Some(x.value),
+        _ => None,
+    }
+}
+
+// negative values are written as the parser reads them, a minus before a
+// literal, so the result prints and parses back the same
+fn integer_literal(value: i64, span: Span) -> Option<Expression> {
+    let literal = |value: i64| {
+        Expression::IntegerLiteral(IntegerLiteral {
+            token: Token {
+                token_type: TokenType::INT,
+                literal: value.to_string(),
+                span,
+            },
+            value,
+        })
+    };
+
+    if value >= 0 {
+        return Some(literal(value));
+    }
+
+    Some(Expression::PrefixExpression(PrefixExpression {
+        token: Token {
+            token_type: TokenType::MINUS,
+            literal: "-".to_string(),
+            span,
+        },
+        operator: "-".to_string(),
+        right: Box::new(literal(value.checked_neg()?)),
+    }))
+}
+
+fn boolean_literal(value: bool, span: Span) -> Expression {
+    let token_type = if value {
+        TokenType::TRUE
+    } else {
+        TokenType::FALSE
+    };
+
+    Expression::Boolean(Boolean {
+        token: Token {
+            token_type,
+            literal: value.to_string(),
+            span,
+        },
+        value,
+    })
+}
@@ -1,37 +1,59 @@
 use super::*;
+use crate::lexer::token::Span;
+use crate::object::BigInt;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum Expression {
     Identifier(Identifier),
     IntegerLiteral(IntegerLiteral),
+    BigIntLiteral(BigIntLiteral),
     PrefixExpression(PrefixExpression),
     InfixExpression(InfixExpression),
     Boolean(Boolean),
+    NullLiteral(NullLiteral),
     IfExpression(IfExpression),
     FunctionLiteral(FunctionLiteral),
     CallExpression(CallExpression),
+    #[default]
     ILLEGAL,
 }
 
 impl Expression {
-    pub fn to_string(&self) -> String {
+    // spans of if expressions and function literals only cover their keyword
+    pub fn span(&self) -> Span {
         match self {
-            Expression::Identifier(ref x) => x.value.to_string(),
-            Expression::IntegerLiteral(ref x) => x.token.literal.to_string(),
-            Expression::PrefixExpression(ref x) => {
-                format!("({}{})", x.operator, x.right.to_string())
+            Expression::Identifier(ref x) => x.token.span,
+            Expression::IntegerLiteral(ref x) => x.token.span,
+            Expression::BigIntLiteral(ref x) => x.token.span,
+            Expression::Boolean(ref x) => x.token.span,
+            Expression::NullLiteral(ref x) => x.token.span,
+            Expression::PrefixExpression(ref x) => x.token.span.to(&x.right.span()),
+            Expression::InfixExpression(ref x) => x.left.span().to(&x.right.span()),
+            Expression::IfExpression(ref x) => x.token.span,
+            Expression::FunctionLiteral(ref x) => x.token.span,
+            Expression::CallExpression(ref x) => x.function.span().to(&x.token.span),
+            Expression::ILLEGAL => Span::default(),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Identifier(ref x) => write!(f, "{}", x.value),
+            Expression::IntegerLiteral(ref x) => write!(f, "{}", x.token.literal),
+            Expression::BigIntLiteral(ref x) => write!(f, "{}", x.token.literal),
+            Expression::PrefixExpression(ref x) => write!(f, "({}{})", x.operator, x.right),
+            Expression::InfixExpression(ref x) => {
+                write!(f, "({} {} {})", x.left, x.operator, x.right)
             }
-            Expression::InfixExpression(ref x) => format!(
-                "({} {} {})",
-                x.left.to_string(),
-                x.operator,
-                x.right.to_string()
-            ),
-            Expression::Boolean(ref x) => x.token.literal.to_string(),
-            Expression::FunctionLiteral(x) => x.to_string(),
-            Expression::IfExpression(x) => x.to_string(),
-            Expression::CallExpression(x) => x.to_string(),
-            Expression::ILLEGAL => panic!(),
+            Expression::Boolean(ref x) => write!(f, "{}", x.token.literal),
+            Expression::NullLiteral(ref x) => write!(f, "{}", x.token.literal),
+            Expression::FunctionLiteral(x) => write!(f, "{}", x),
+            Expression::IfExpression(x) => write!(f, "{}", x),
+            Expression::CallExpression(x) => write!(f, "{}", x),
+            Expression::ILLEGAL => write!(f, "ILLEGAL"),
         }
     }
 }
@@ -46,26 +68,25 @@ impl Node for Expression {
             Expression::PrefixExpression(x) => AST::PrefixExpression(x.clone()),
             Expression::InfixExpression(x) => AST::InfixExpression(x.clone()),
             Expression::IntegerLiteral(x) => AST::IntegerLiteral(x.clone()),
+            Expression::BigIntLiteral(x) => AST::BigIntLiteral(x.clone()),
             Expression::IfExpression(x) => AST::IfExpression(x.clone()),
             Expression::Boolean(x) => AST::Boolean(x.clone()),
-            _ => unimplemented!(),
+            Expression::NullLiteral(x) => AST::NullLiteral(x.clone()),
+            Expression::Identifier(x) => AST::Identifier(x.clone()),
+            Expression::FunctionLiteral(x) => AST::FunctionLiteral(x.clone()),
+            Expression::CallExpression(x) => AST::CallExpression(x.clone()),
+            Expression::ILLEGAL => unimplemented!(),
         }
     }
 }
 
-impl Default for Expression {
-    fn default() -> Expression {
-        Expression::ILLEGAL
-    }
-}
-
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Identifier {
     pub token: Token,
     pub value: String,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct IntegerLiteral {
     pub token: Token,
     pub value: i64,
@@ -81,14 +102,31 @@ impl Node for IntegerLiteral {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+// an integer literal too large for an IntegerLiteral
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BigIntLiteral {
+    pub token: Token,
+    pub value: BigInt,
+}
+
+impl Node for BigIntLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn to_ast(&self) -> AST {
+        AST::BigIntLiteral(self.clone())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PrefixExpression {
     pub token: Token,
     pub operator: String,
     pub right: Box<Expression>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct InfixExpression {
     pub token: Token,
     pub operator: String,
@@ -106,13 +144,18 @@ impl Node for InfixExpression {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Boolean {
     pub token: Token,
     pub value: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NullLiteral {
+    pub token: Token,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct IfExpression {
     pub token: Token,
     pub condition: Box<Expression>,
@@ -120,36 +163,43 @@ pub struct IfExpression {
     pub alternative: Option<BlockStatement>,
 }
 
-impl IfExpression {
-    pub fn to_string(&self) -> String {
-        let mut if_string = format!(
-            "if {} {}",
-            self.condition.to_string(),
-            self.consequence.to_string()
-        );
+impl fmt::Display for IfExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // infix and prefix conditions already print their own parentheses
+        match *self.condition {
+            Expression::InfixExpression(_) | Expression::PrefixExpression(_) => {
+                write!(f, "if {} ", self.condition)?
+            }
+            _ => write!(f, "if ({}) ", self.condition)?,
+        }
+        write_block(f, &self.consequence)?;
 
         if let Some(alt) = &self.alternative {
-            if_string.push_str(&alt.to_string());
+            write!(f, " else ")?;
+            write_block(f, alt)?;
         }
 
-        if_string
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct BlockStatement {
     pub token: Token,
     pub statements: Vec<Statements>,
 }
 
-impl BlockStatement {
-    pub fn to_string(&self) -> String {
-        let mut return_string = String::new();
-        for stmt in &self.statements {
-            return_string.push_str(&stmt.to_string());
-        }
+impl fmt::Display for BlockStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_statements(f, &self.statements)
+    }
+}
 
-        return_string
+fn write_block(f: &mut fmt::Formatter, block: &BlockStatement) -> fmt::Result {
+    if block.statements.is_empty() {
+        write!(f, "{{}}")
+    } else {
+        write!(f, "{{ {} }}", block)
     }
 }
 
@@ -163,45 +213,34 @@ impl Node for BlockStatement {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct FunctionLiteral {
     pub token: Token,
     pub parameters: Vec<Identifier>,
     pub body: BlockStatement,
 }
 
-impl FunctionLiteral {
-    pub fn to_string(&self) -> String {
-        let mut return_string = String::new();
-        for param in &self.parameters {
-            return_string.push_str(&param.value.to_string());
-            return_string.push(',');
-        }
+impl fmt::Display for FunctionLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parameters: Vec<&str> = self.parameters.iter().map(|x| x.value.as_str()).collect();
 
-        format!(
-            "{}({}) {}",
-            self.token.literal,
-            return_string,
-            self.body.to_string()
-        )
+        write!(f, "{}({}) ", self.token.literal, parameters.join(", "))?;
+        write_block(f, &self.body)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CallExpression {
     pub token: Token,
     pub function: Box<Expression>,
     pub arguments: Vec<Expression>,
 }
 
-impl CallExpression {
-    pub fn to_string(&self) -> String {
-        let mut return_string = String::new();
-        for arg in &self.arguments {
-            return_string.push_str(&arg.to_string());
-        }
+impl fmt::Display for CallExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments: Vec<String> = self.arguments.iter().map(|x| x.to_string()).collect();
 
-        format!("{}({})", self.function.to_string(), return_string,)
+        write!(f, "{}({})", self.function, arguments.join(", "))
     }
 }
 
@@ -0,0 +1,266 @@
+use super::expressions::*;
+use super::statements::*;
+use super::Program;
+
+// rebuilds the ast from owned nodes. every method defaults to folding the
+// node's children, so a pass that replaces a kind of node, say an infix
+// expression with a literal, overrides fold_expression and leaves the rest
+pub trait Fold {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+
+    fn fold_statement(&mut self, statement: Statements) -> Statements {
+        walk_statement(self, statement)
+    }
+
+    fn fold_let_statement(&mut self, statement: LetStatement) -> LetStatement {
+        walk_let_statement(self, statement)
+    }
+
+    fn fold_assign_statement(&mut self, statement: AssignStatement) -> AssignStatement {
+        walk_assign_statement(self, statement)
+    }
+
+    fn fold_return_statement(&mut self, statement: ReturnStatement) -> ReturnStatement {
+        walk_return_statement(self, statement)
+    }
+
+    fn fold_expression_statement(&mut self, statement: ExpressionStatement) -> ExpressionStatement {
+        walk_expression_statement(self, statement)
+    }
+
+    fn fold_block(&mut self, block: BlockStatement) -> BlockStatement {
+        walk_block(self, block)
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        walk_expression(self, expression)
+    }
+
+    // a name being used. names being bound go to fold_binding
+    fn fold_identifier(&mut self, identifier: Identifier) -> Identifier {
+        identifier
+    }
+
+    // the name of a let statement or a function parameter
+    fn fold_binding(&mut self, identifier: Identifier) -> Identifier {
+        identifier
+    }
+
+    fn fold_integer_literal(&mut self, literal: IntegerLiteral) -> IntegerLiteral {
+        literal
+    }
+
+    fn fold_big_int_literal(&mut self, literal: BigIntLiteral) -> BigIntLiteral {
+        literal
+    }
+
+    fn fold_boolean(&mut self, boolean: Boolean) -> Boolean {
+        boolean
+    }
+
+    fn fold_null_literal(&mut self, literal: NullLiteral) -> NullLiteral {
+        literal
+    }
+
+    fn fold_prefix_expression(&mut self, expression: PrefixExpression) -> PrefixExpression {
+        walk_prefix_expression(self, expression)
+    }
+
+    fn fold_infix_expression(&mut self, expression: InfixExpression) -> InfixExpression {
+        walk_infix_expression(self, expression)
+    }
+
+    fn fold_if_expression(&mut self, expression: IfExpression) -> IfExpression {
+        walk_if_expression(self, expression)
+    }
+
+    fn fold_function_literal(&mut self, function: FunctionLiteral) -> FunctionLiteral {
+        walk_function_literal(self, function)
+    }
+
+    fn fold_call_expression(&mut self, call: CallExpression) -> CallExpression {
+        walk_call_expression(self, call)
+    }
+}
+
+pub fn walk_program<F: Fold + ?Sized>(folder: &mut F, program: Program) -> Program {
+    Program {
+        statements: program
+            .statements
+            .into_iter()
+            .map(|x| folder.fold_statement(x))
+            .collect(),
+    }
+}
+
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, statement: Statements) -> Statements {
+    match statement {
+        Statements::LetStatement(x) => Statements::LetStatement(folder.fold_let_statement(x)),
+        Statements::AssignStatement(x) => {
+            Statements::AssignStatement(folder.fold_assign_statement(x))
+        }
+        Statements::ReturnStatement(x) => {
+            Statements::ReturnStatement(folder.fold_return_statement(x))
+        }
+        Statements::ExpressionStatement(x) => {
+            Statements::ExpressionStatement(folder.fold_expression_statement(x))
+        }
+    }
+}
+
+pub fn walk_let_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: LetStatement,
+) -> LetStatement {
+    let value = folder.fold_expression(statement.value);
+
+    LetStatement {
+        token: statement.token,
+        name: folder.fold_binding(statement.name),
+        value,
+    }
+}
+
+// the name is a use, not a binding, as it must already be bound
+pub fn walk_assign_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: AssignStatement,
+) -> AssignStatement {
+    let value = folder.fold_expression(statement.value);
+
+    AssignStatement {
+        token: statement.token,
+        name: folder.fold_identifier(statement.name),
+        operator: statement.operator,
+        value,
+    }
+}
+
+pub fn walk_return_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: ReturnStatement,
+) -> ReturnStatement {
+    ReturnStatement {
+        token: statement.token,
+        return_value: folder.fold_expression(statement.return_value),
+    }
+}
+
+pub fn walk_expression_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: ExpressionStatement,
+) -> ExpressionStatement {
+    ExpressionStatement {
+        token: statement.token,
+        expression: folder.fold_expression(statement.expression),
+    }
+}
+
+pub fn walk_block<F: Fold + ?Sized>(folder: &mut F, block: BlockStatement) -> BlockStatement {
+    BlockStatement {
+        token: block.token,
+        statements: block
+            .statements
+            .into_iter()
+            .map(|x| folder.fold_statement(x))
+            .collect(),
+    }
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression {
+        Expression::Identifier(x) => Expression::Identifier(folder.fold_identifier(x)),
+        Expression::IntegerLiteral(x) => Expression::IntegerLiteral(folder.fold_integer_literal(x)),
+        Expression::BigIntLiteral(x) => Expression::BigIntLiteral(folder.fold_big_int_literal(x)),
+        Expression::Boolean(x) => Expression::Boolean(folder.fold_boolean(x)),
+        Expression::NullLiteral(x) => Expression::NullLiteral(folder.fold_null_literal(x)),
+        Expression::PrefixExpression(x) => {
+            Expression::PrefixExpression(folder.fold_prefix_expression(x))
+        }
+        Expression::InfixExpression(x) => {
+            Expression::InfixExpression(folder.fold_infix_expression(x))
+        }
+        Expression::IfExpression(x) => Expression::IfExpression(folder.fold_if_expression(x)),
+        Expression::FunctionLiteral(x) => {
+            Expression::FunctionLiteral(folder.fold_function_literal(x))
+        }
+        Expression::CallExpression(x) => Expression::CallExpression(folder.fold_call_expression(x)),
+        Expression::ILLEGAL => Expression::ILLEGAL,
+    }
+}
+
+pub fn walk_prefix_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    expression: PrefixExpression,
+) -> PrefixExpression {
+    PrefixExpression {
+        token: expression.token,
+        operator: expression.operator,
+        right: Box::new(folder.fold_expression(*expression.right)),
+    }
+}
+
+pub fn walk_infix_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    expression: InfixExpression,
+) -> InfixExpression {
+    let left = Box::new(folder.fold_expression(*expression.left));
+
+    InfixExpression {
+        token: expression.token,
+        operator: expression.operator,
+        left,
+        right: Box::new(folder.fold_expression(*expression.right)),
+    }
+}
+
+pub fn walk_if_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    expression: IfExpression,
+) -> IfExpression {
+    let condition = Box::new(folder.fold_expression(*expression.condition));
+    let consequence = folder.fold_block(expression.consequence);
+
+    IfExpression {
+        token: expression.token,
+        condition,
+        consequence,
+        alternative: expression.alternative.map(|x| folder.fold_block(x)),
+    }
+}
+
+pub fn walk_function_literal<F: Fold + ?Sized>(
+    folder: &mut F,
+    function: FunctionLiteral,
+) -> FunctionLiteral {
+    let parameters = function
+        .parameters
+        .into_iter()
+        .map(|x| folder.fold_binding(x))
+        .collect();
+
+    FunctionLiteral {
+        token: function.token,
+        parameters,
+        body: folder.fold_block(function.body),
+    }
+}
+
+pub fn walk_call_expression<F: Fold + ?Sized>(
+    folder: &mut F,
+    call: CallExpression,
+) -> CallExpression {
+    let function = Box::new(folder.fold_expression(*call.function));
+
+    CallExpression {
+        token: call.token,
+        function,
+        arguments: call
+            .arguments
+            .into_iter()
+            .map(|x| folder.fold_expression(x))
+            .collect(),
+    }
+}
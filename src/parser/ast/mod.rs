@@ -1,21 +1,33 @@
 pub mod expressions;
+pub mod fold;
+pub mod printer;
 pub mod statements;
+pub mod visit;
+pub mod visit_mut;
 
 use self::expressions::*;
 use self::statements::*;
 use crate::lexer::token::Token;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum AST {
     Program(Program),
+    LetStatement(LetStatement),
+    AssignStatement(AssignStatement),
+    ReturnStatement(ReturnStatement),
     ExpressionStatement(ExpressionStatement),
     IntegerLiteral(IntegerLiteral),
+    BigIntLiteral(BigIntLiteral),
     Boolean(Boolean),
+    NullLiteral(NullLiteral),
     Identifier(Identifier),
     PrefixExpression(PrefixExpression),
     InfixExpression(InfixExpression),
     BlockStatement(BlockStatement),
     IfExpression(IfExpression),
+    FunctionLiteral(FunctionLiteral),
+    CallExpression(CallExpression),
 }
 
 pub trait Node {
@@ -32,21 +44,33 @@ pub trait Expr: Node {
 }
 
 // root ast node
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Program {
     pub statements: Vec<Statements>,
 }
 
-impl Program {
-    pub fn to_string(&self) -> String {
-        let mut string = String::new();
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_statements(f, &self.statements)
+    }
+}
 
-        for statement in &self.statements {
-            string.push_str(&statement.to_string());
+// expression statements need a semicolon to keep them apart from the next statement
+fn write_statements(f: &mut fmt::Formatter, statements: &[Statements]) -> fmt::Result {
+    for (i, statement) in statements.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
         }
+        write!(f, "{}", statement)?;
 
-        string
+        if let Statements::ExpressionStatement(_) = statement {
+            if i + 1 < statements.len() {
+                write!(f, ";")?;
+            }
+        }
     }
+
+    Ok(())
 }
 
 impl Node for Program {
@@ -0,0 +1,373 @@
+use super::expressions::*;
+use super::statements::*;
+use super::Program;
+use crate::lexer::token::TokenType;
+use crate::parser::Precedence;
+use std::collections::{HashMap, HashSet};
+
+const INDENT: &str = "    ";
+
+// calls that would run past this column get one argument per line
+pub const MAX_WIDTH: usize = 80;
+
+// prints source that parses back to the same ast: one statement per line,
+// blocks indented by four spaces, and parentheses only where precedence needs them
+pub fn print(program: &Program) -> String {
+    print_with_layout(program, Layout::default())
+}
+
+// like print, but puts back the comments and blank lines of the source
+pub fn print_with_layout(program: &Program, layout: Layout) -> String {
+    let mut printer = Printer {
+        layout,
+        ..Printer::default()
+    };
+    printer.statements(&program.statements, usize::MAX, false);
+    printer.output
+}
+
+pub fn print_expression(expression: &Expression) -> String {
+    let mut printer = Printer::default();
+    printer.expression(expression);
+    printer.output
+}
+
+// a function object has no literal of its own, only what it was built from
+pub fn print_function(parameters: &[Identifier], body: &BlockStatement) -> String {
+    let mut printer = Printer::default();
+    printer.function(parameters, body);
+    printer.output
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    // char offset of the leading //
+    pub start: usize,
+    pub line: usize,
+    pub text: String,
+    // written after code on the same line
+    pub trailing: bool,
+}
+
+// what the ast does not keep about the source it was parsed from
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    // in source order
+    pub comments: Vec<Comment>,
+    pub blank_lines: HashSet<usize>,
+    // offset of each { to the offset of the } closing it
+    pub closing_braces: HashMap<usize, usize>,
+    // offset of each ) to the offset of the ( opening it
+    pub opening_parens: HashMap<usize, usize>,
+}
+
+#[derive(Default)]
+struct Printer {
+    output: String,
+    depth: usize,
+    layout: Layout,
+    next_comment: usize,
+    // set while measuring how long a call is on one line
+    no_wrap: bool,
+}
+
+impl Printer {
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn new_line(&mut self) {
+        self.output.push('\n');
+        self.output.push_str(&INDENT.repeat(self.depth));
+    }
+
+    fn column(&self) -> usize {
+        match self.output.rfind('\n') {
+            Some(x) => self.output[x + 1..].chars().count(),
+            None => self.output.chars().count(),
+        }
+    }
+
+    // starts the line for something written on line of the source, keeping
+    // a single blank line above it if there was one
+    fn start_line(&mut self, line: usize) {
+        if self.output.is_empty() {
+            return;
+        }
+
+        if self.layout.blank_lines.contains(&(line - 1)) && !self.output.ends_with('{') {
+            self.output.push('\n');
+        }
+        self.new_line();
+    }
+
+    fn has_comment_before(&self, offset: usize) -> bool {
+        self.layout
+            .comments
+            .get(self.next_comment)
+            .is_some_and(|x| x.start < offset)
+    }
+
+    fn has_comment_between(&self, start: usize, end: usize) -> bool {
+        self.layout.comments[self.next_comment..]
+            .iter()
+            .take_while(|x| x.start < end)
+            .any(|x| x.start > start)
+    }
+
+    fn comments_before(&mut self, offset: usize) {
+        while self.has_comment_before(offset) {
+            let comment = self.layout.comments[self.next_comment].clone();
+            self.next_comment += 1;
+
+            if comment.trailing && !self.output.is_empty() {
+                self.write(" ");
+            } else {
+                self.start_line(comment.line);
+            }
+            self.write(&comment.text);
+        }
+    }
+
+    // the last expression of a block is its value and is left without a semicolon
+    fn statements(&mut self, statements: &[Statements], end: usize, in_block: bool) {
+        for (i, statement) in statements.iter().enumerate() {
+            let token = match statement {
+                Statements::LetStatement(x) => &x.token,
+                Statements::AssignStatement(x) => &x.name.token,
+                Statements::ReturnStatement(x) => &x.token,
+                Statements::ExpressionStatement(x) => &x.token,
+            };
+
+            self.comments_before(token.span.start);
+            self.start_line(token.span.line);
+            self.statement(statement);
+
+            let next = statements.get(i + 1);
+            if let Statements::ExpressionStatement(x) = statement {
+                if needs_semicolon(&x.expression, next, in_block) {
+                    self.write(";");
+                }
+            }
+        }
+
+        self.comments_before(end);
+
+        if in_block {
+            self.depth -= 1;
+            self.new_line();
+            self.depth += 1;
+        } else if !self.output.is_empty() {
+            self.write("\n");
+        }
+    }
+
+    fn statement(&mut self, statement: &Statements) {
+        match statement {
+            Statements::LetStatement(x) => {
+                self.write(&format!("{} {} = ", x.token.literal, x.name.value));
+                self.expression(&x.value);
+                self.write(";");
+            }
+            Statements::AssignStatement(x) => {
+                self.write(&format!("{} {} ", x.name.value, x.operator));
+                self.expression(&x.value);
+                self.write(";");
+            }
+            Statements::ReturnStatement(x) => {
+                self.write("return ");
+                self.expression(&x.return_value);
+                self.write(";");
+            }
+            Statements::ExpressionStatement(x) => self.expression(&x.expression),
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(x) => self.write(&x.value),
+            Expression::IntegerLiteral(x) => self.write(&x.token.literal),
+            Expression::BigIntLiteral(x) => self.write(&x.token.literal),
+            Expression::Boolean(x) => self.write(&x.token.literal),
+            Expression::NullLiteral(x) => self.write(&x.token.literal),
+            Expression::PrefixExpression(x) => {
+                self.write(&x.operator);
+                self.operand(&x.right, |operand| operand < Precedence::PREFIX);
+            }
+            Expression::InfixExpression(x) if x.token.token_type == TokenType::POWER => {
+                // right associative, and its right side may be a prefix
+                // expression, as in `2 ** -1`
+                self.operand(&x.left, |operand| operand <= Precedence::EXPONENT);
+                self.write(&format!(" {} ", x.operator));
+                self.operand(&x.right, |operand| operand < Precedence::PREFIX);
+            }
+            Expression::InfixExpression(x) => {
+                let precedence = Precedence::from_token(&x.token.token_type);
+
+                // the other operators are left associative, so only the right
+                // side needs parentheses at equal precedence
+                self.operand(&x.left, |operand| operand < precedence);
+                self.write(&format!(" {} ", x.operator));
+                self.operand(&x.right, |operand| operand <= precedence);
+            }
+            Expression::IfExpression(x) => {
+                self.write("if (");
+                self.expression(&x.condition);
+                self.write(") ");
+                self.block(&x.consequence);
+
+                if let Some(alternative) = &x.alternative {
+                    self.write(" else ");
+                    self.block(alternative);
+                }
+            }
+            Expression::FunctionLiteral(x) => self.function(&x.parameters, &x.body),
+            Expression::CallExpression(x) => self.call(x),
+            Expression::ILLEGAL => self.write("ILLEGAL"),
+        }
+    }
+
+    fn call(&mut self, call: &CallExpression) {
+        // the token of a call is its closing parenthesis. a comment among the
+        // arguments stays with them, so the call is wrapped whatever its length
+        let end = call.token.span.start;
+        let has_comment = match self.layout.opening_parens.get(&end) {
+            Some(&start) => self.has_comment_between(start, end),
+            None => false,
+        };
+        if has_comment {
+            self.wrapped_call(call);
+            return;
+        }
+
+        // measure on one line first, keeping nested calls unwrapped so the
+        // outermost call is the one that wraps
+        let start = self.output.len();
+        let next_comment = self.next_comment;
+        let no_wrap = self.no_wrap;
+
+        self.no_wrap = true;
+        self.call_on_one_line(call);
+        self.no_wrap = no_wrap;
+
+        // leave room for the semicolon or comma that usually follows
+        let multiline = self.output[start..].contains('\n');
+        if no_wrap || (!multiline && self.column() < MAX_WIDTH) {
+            return;
+        }
+
+        self.output.truncate(start);
+        self.next_comment = next_comment;
+
+        // arguments spanning lines, like function literals, are left as they are
+        if multiline || call.arguments.is_empty() {
+            self.call_on_one_line(call);
+            return;
+        }
+
+        self.wrapped_call(call);
+    }
+
+    // one argument per line, each followed by the comments written after it
+    fn wrapped_call(&mut self, call: &CallExpression) {
+        self.operand(&call.function, |operand| operand < Precedence::CALL);
+        self.write("(");
+        self.depth += 1;
+
+        for (i, argument) in call.arguments.iter().enumerate() {
+            self.comments_before(argument.span().start);
+            self.new_line();
+            self.expression(argument);
+            if i + 1 < call.arguments.len() {
+                self.write(",");
+            }
+        }
+
+        self.comments_before(call.token.span.start);
+        self.depth -= 1;
+        self.new_line();
+        self.write(")");
+    }
+
+    fn call_on_one_line(&mut self, call: &CallExpression) {
+        self.operand(&call.function, |operand| operand < Precedence::CALL);
+        self.write("(");
+
+        for (i, argument) in call.arguments.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.expression(argument);
+        }
+
+        self.write(")");
+    }
+
+    fn operand(&mut self, expression: &Expression, needs_parentheses: impl Fn(Precedence) -> bool) {
+        if needs_parentheses(precedence(expression)) {
+            self.write("(");
+            self.expression(expression);
+            self.write(")");
+        } else {
+            self.expression(expression);
+        }
+    }
+
+    fn function(&mut self, parameters: &[Identifier], body: &BlockStatement) {
+        let parameters: Vec<&str> = parameters.iter().map(|x| x.value.as_str()).collect();
+
+        self.write(&format!("fn({}) ", parameters.join(", ")));
+        self.block(body);
+    }
+
+    fn block(&mut self, block: &BlockStatement) {
+        let end = self
+            .layout
+            .closing_braces
+            .get(&block.token.span.start)
+            .cloned()
+            .unwrap_or(usize::MAX);
+
+        if block.statements.is_empty() && !self.has_comment_before(end) {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{");
+        self.depth += 1;
+        self.statements(&block.statements, end, true);
+        self.depth -= 1;
+        self.write("}");
+    }
+}
+
+// how tightly an expression binds. literals, names, ifs and functions never
+// need parentheses, as they bind like a call
+fn precedence(expression: &Expression) -> Precedence {
+    match expression {
+        Expression::InfixExpression(x) => Precedence::from_token(&x.token.token_type),
+        Expression::PrefixExpression(_) => Precedence::PREFIX,
+        _ => Precedence::CALL,
+    }
+}
+
+// a statement without a semicolon runs on into the next one when that starts
+// with `-` or `(`, which would continue it as a subtraction or a call
+fn needs_semicolon(expression: &Expression, next: Option<&Statements>, in_block: bool) -> bool {
+    let next = match next {
+        Some(x) => x,
+        None => return !in_block && !matches!(expression, Expression::IfExpression(_)),
+    };
+
+    if !matches!(expression, Expression::IfExpression(_)) {
+        return true;
+    }
+
+    match next {
+        Statements::ExpressionStatement(x) => {
+            let text = print_expression(&x.expression);
+            text.starts_with('-') || text.starts_with('(')
+        }
+        _ => false,
+    }
+}
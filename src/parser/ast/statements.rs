@@ -1,18 +1,22 @@
 use super::*;
+use crate::lexer::token::TokenType;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Statements {
     LetStatement(LetStatement),
+    AssignStatement(AssignStatement),
     ReturnStatement(ReturnStatement),
     ExpressionStatement(ExpressionStatement),
 }
 
-impl Statements {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Statements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Statements::LetStatement(ref x) => x.to_string(),
-            Statements::ReturnStatement(ref x) => x.to_string(),
-            Statements::ExpressionStatement(ref x) => x.to_string(),
+            Statements::LetStatement(ref x) => write!(f, "{}", x),
+            Statements::AssignStatement(ref x) => write!(f, "{}", x),
+            Statements::ReturnStatement(ref x) => write!(f, "{}", x),
+            Statements::ExpressionStatement(ref x) => write!(f, "{}", x),
         }
     }
 }
@@ -21,17 +25,24 @@ impl Node for Statements {
     fn token_literal(&self) -> String {
         match self {
             Statements::LetStatement(ref x) => x.token.literal.clone(),
+            Statements::AssignStatement(ref x) => x.token.literal.clone(),
             Statements::ReturnStatement(ref x) => x.token.literal.clone(),
             Statements::ExpressionStatement(ref x) => x.token.literal.clone(),
         }
     }
 
     fn to_ast(&self) -> AST {
-        unimplemented!()
+        match self {
+            Statements::LetStatement(ref x) => AST::LetStatement(x.clone()),
+            Statements::AssignStatement(ref x) => AST::AssignStatement(x.clone()),
+            Statements::ReturnStatement(ref x) => AST::ReturnStatement(x.clone()),
+            Statements::ExpressionStatement(ref x) => AST::ExpressionStatement(x.clone()),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+// `let x = value` or `const x = value`. the token is the keyword
+#[derive(Debug, Clone, PartialEq)]
 pub struct LetStatement {
     pub token: Token,
     pub name: Identifier,
@@ -39,25 +50,46 @@ pub struct LetStatement {
 }
 
 impl LetStatement {
-    pub fn to_string(&self) -> String {
-        format!(
+    // a constant cannot be assigned to, nor redeclared in its own scope
+    pub fn is_const(&self) -> bool {
+        self.token.token_type == TokenType::CONST
+    }
+}
+
+impl fmt::Display for LetStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "{} {} = {};",
-            self.token.literal,
-            self.name.value,
-            self.value.to_string()
+            self.token.literal, self.name.value, self.value
         )
     }
 }
 
-#[derive(Debug, Clone)]
+// `x = value`, or `x += value` and the like. the token is the operator
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssignStatement {
+    pub token: Token,
+    pub name: Identifier,
+    pub operator: String,
+    pub value: Expression,
+}
+
+impl fmt::Display for AssignStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {};", self.name.value, self.operator, self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReturnStatement {
     pub token: Token,
     pub return_value: Expression,
 }
 
-impl ReturnStatement {
-    pub fn to_string(&self) -> String {
-        format!("{} {};", self.token.literal, self.return_value.to_string(),)
+impl fmt::Display for ReturnStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {};", self.token.literal, self.return_value)
     }
 }
 
@@ -67,13 +99,21 @@ pub struct ExpressionStatement {
     pub expression: Expression,
 }
 
-impl ExpressionStatement {
-    pub fn to_string(&self) -> String {
-        self.expression.to_string()
+// the token is wherever the statement started, which may be a parenthesis that
+// does not show up in the tree, so it is left out of the comparison
+impl PartialEq for ExpressionStatement {
+    fn eq(&self, other: &ExpressionStatement) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl fmt::Display for ExpressionStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expression)
     }
 }
 
-impl Node for ExpressionStatement  {
+impl Node for ExpressionStatement {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
@@ -0,0 +1,168 @@
+use super::expressions::*;
+use super::statements::*;
+use super::Program;
+
+// walks the ast by reference. every method defaults to visiting the node's
+// children, so a pass only overrides the nodes it cares about and calls the
+// matching walk function to keep going below them
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, statement: &Statements) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_let_statement(&mut self, statement: &LetStatement) {
+        walk_let_statement(self, statement);
+    }
+
+    fn visit_assign_statement(&mut self, statement: &AssignStatement) {
+        walk_assign_statement(self, statement);
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) {
+        walk_return_statement(self, statement);
+    }
+
+    fn visit_expression_statement(&mut self, statement: &ExpressionStatement) {
+        walk_expression_statement(self, statement);
+    }
+
+    fn visit_block(&mut self, block: &BlockStatement) {
+        walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    // a name being used. names being bound go to visit_binding
+    fn visit_identifier(&mut self, _identifier: &Identifier) {}
+
+    // the name of a let statement or a function parameter
+    fn visit_binding(&mut self, _identifier: &Identifier) {}
+
+    fn visit_integer_literal(&mut self, _literal: &IntegerLiteral) {}
+
+    fn visit_big_int_literal(&mut self, _literal: &BigIntLiteral) {}
+
+    fn visit_boolean(&mut self, _boolean: &Boolean) {}
+
+    fn visit_null_literal(&mut self, _literal: &NullLiteral) {}
+
+    fn visit_prefix_expression(&mut self, expression: &PrefixExpression) {
+        walk_prefix_expression(self, expression);
+    }
+
+    fn visit_infix_expression(&mut self, expression: &InfixExpression) {
+        walk_infix_expression(self, expression);
+    }
+
+    fn visit_if_expression(&mut self, expression: &IfExpression) {
+        walk_if_expression(self, expression);
+    }
+
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        walk_function_literal(self, function);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression) {
+        walk_call_expression(self, call);
+    }
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    for statement in &program.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statements) {
+    match statement {
+        Statements::LetStatement(x) => visitor.visit_let_statement(x),
+        Statements::AssignStatement(x) => visitor.visit_assign_statement(x),
+        Statements::ReturnStatement(x) => visitor.visit_return_statement(x),
+        Statements::ExpressionStatement(x) => visitor.visit_expression_statement(x),
+    }
+}
+
+// the value comes before the binding, as it is evaluated before the name exists
+pub fn walk_let_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &LetStatement) {
+    visitor.visit_expression(&statement.value);
+    visitor.visit_binding(&statement.name);
+}
+
+// the name is a use, not a binding, as it must already be bound
+pub fn walk_assign_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &AssignStatement) {
+    visitor.visit_expression(&statement.value);
+    visitor.visit_identifier(&statement.name);
+}
+
+pub fn walk_return_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &ReturnStatement) {
+    visitor.visit_expression(&statement.return_value);
+}
+
+pub fn walk_expression_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    statement: &ExpressionStatement,
+) {
+    visitor.visit_expression(&statement.expression);
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &BlockStatement) {
+    for statement in &block.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Identifier(x) => visitor.visit_identifier(x),
+        Expression::IntegerLiteral(x) => visitor.visit_integer_literal(x),
+        Expression::BigIntLiteral(x) => visitor.visit_big_int_literal(x),
+        Expression::Boolean(x) => visitor.visit_boolean(x),
+        Expression::NullLiteral(x) => visitor.visit_null_literal(x),
+        Expression::PrefixExpression(x) => visitor.visit_prefix_expression(x),
+        Expression::InfixExpression(x) => visitor.visit_infix_expression(x),
+        Expression::IfExpression(x) => visitor.visit_if_expression(x),
+        Expression::FunctionLiteral(x) => visitor.visit_function_literal(x),
+        Expression::CallExpression(x) => visitor.visit_call_expression(x),
+        Expression::ILLEGAL => (),
+    }
+}
+
+pub fn walk_prefix_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &PrefixExpression) {
+    visitor.visit_expression(&expression.right);
+}
+
+pub fn walk_infix_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &InfixExpression) {
+    visitor.visit_expression(&expression.left);
+    visitor.visit_expression(&expression.right);
+}
+
+pub fn walk_if_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &IfExpression) {
+    visitor.visit_expression(&expression.condition);
+    visitor.visit_block(&expression.consequence);
+
+    if let Some(alternative) = &expression.alternative {
+        visitor.visit_block(alternative);
+    }
+}
+
+pub fn walk_function_literal<V: Visitor + ?Sized>(visitor: &mut V, function: &FunctionLiteral) {
+    for parameter in &function.parameters {
+        visitor.visit_binding(parameter);
+    }
+
+    visitor.visit_block(&function.body);
+}
+
+pub fn walk_call_expression<V: Visitor + ?Sized>(visitor: &mut V, call: &CallExpression) {
+    visitor.visit_expression(&call.function);
+
+    for argument in &call.arguments {
+        visitor.visit_expression(argument);
+    }
+}
@@ -0,0 +1,190 @@
+use super::expressions::*;
+use super::statements::*;
+use super::Program;
+
+// like Visitor, but can change the nodes in place
+pub trait VisitorMut {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statements) {
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_let_statement_mut(&mut self, statement: &mut LetStatement) {
+        walk_let_statement_mut(self, statement);
+    }
+
+    fn visit_assign_statement_mut(&mut self, statement: &mut AssignStatement) {
+        walk_assign_statement_mut(self, statement);
+    }
+
+    fn visit_return_statement_mut(&mut self, statement: &mut ReturnStatement) {
+        walk_return_statement_mut(self, statement);
+    }
+
+    fn visit_expression_statement_mut(&mut self, statement: &mut ExpressionStatement) {
+        walk_expression_statement_mut(self, statement);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut BlockStatement) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    // a name being used. names being bound go to visit_binding_mut
+    fn visit_identifier_mut(&mut self, _identifier: &mut Identifier) {}
+
+    // the name of a let statement or a function parameter
+    fn visit_binding_mut(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_integer_literal_mut(&mut self, _literal: &mut IntegerLiteral) {}
+
+    fn visit_big_int_literal_mut(&mut self, _literal: &mut BigIntLiteral) {}
+
+    fn visit_boolean_mut(&mut self, _boolean: &mut Boolean) {}
+
+    fn visit_null_literal_mut(&mut self, _literal: &mut NullLiteral) {}
+
+    fn visit_prefix_expression_mut(&mut self, expression: &mut PrefixExpression) {
+        walk_prefix_expression_mut(self, expression);
+    }
+
+    fn visit_infix_expression_mut(&mut self, expression: &mut InfixExpression) {
+        walk_infix_expression_mut(self, expression);
+    }
+
+    fn visit_if_expression_mut(&mut self, expression: &mut IfExpression) {
+        walk_if_expression_mut(self, expression);
+    }
+
+    fn visit_function_literal_mut(&mut self, function: &mut FunctionLiteral) {
+        walk_function_literal_mut(self, function);
+    }
+
+    fn visit_call_expression_mut(&mut self, call: &mut CallExpression) {
+        walk_call_expression_mut(self, call);
+    }
+}
+
+pub fn walk_program_mut<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    for statement in &mut program.statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statements) {
+    match statement {
+        Statements::LetStatement(x) => visitor.visit_let_statement_mut(x),
+        Statements::AssignStatement(x) => visitor.visit_assign_statement_mut(x),
+        Statements::ReturnStatement(x) => visitor.visit_return_statement_mut(x),
+        Statements::ExpressionStatement(x) => visitor.visit_expression_statement_mut(x),
+    }
+}
+
+// the value comes before the binding, as it is evaluated before the name exists
+pub fn walk_let_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut LetStatement,
+) {
+    visitor.visit_expression_mut(&mut statement.value);
+    visitor.visit_binding_mut(&mut statement.name);
+}
+
+// the name is a use, not a binding, as it must already be bound
+pub fn walk_assign_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut AssignStatement,
+) {
+    visitor.visit_expression_mut(&mut statement.value);
+    visitor.visit_identifier_mut(&mut statement.name);
+}
+
+pub fn walk_return_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut ReturnStatement,
+) {
+    visitor.visit_expression_mut(&mut statement.return_value);
+}
+
+pub fn walk_expression_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut ExpressionStatement,
+) {
+    visitor.visit_expression_mut(&mut statement.expression);
+}
+
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut BlockStatement) {
+    for statement in &mut block.statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::Identifier(x) => visitor.visit_identifier_mut(x),
+        Expression::IntegerLiteral(x) => visitor.visit_integer_literal_mut(x),
+        Expression::BigIntLiteral(x) => visitor.visit_big_int_literal_mut(x),
+        Expression::Boolean(x) => visitor.visit_boolean_mut(x),
+        Expression::NullLiteral(x) => visitor.visit_null_literal_mut(x),
+        Expression::PrefixExpression(x) => visitor.visit_prefix_expression_mut(x),
+        Expression::InfixExpression(x) => visitor.visit_infix_expression_mut(x),
+        Expression::IfExpression(x) => visitor.visit_if_expression_mut(x),
+        Expression::FunctionLiteral(x) => visitor.visit_function_literal_mut(x),
+        Expression::CallExpression(x) => visitor.visit_call_expression_mut(x),
+        Expression::ILLEGAL => (),
+    }
+}
+
+pub fn walk_prefix_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expression: &mut PrefixExpression,
+) {
+    visitor.visit_expression_mut(&mut expression.right);
+}
+
+pub fn walk_infix_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expression: &mut InfixExpression,
+) {
+    visitor.visit_expression_mut(&mut expression.left);
+    visitor.visit_expression_mut(&mut expression.right);
+}
+
+pub fn walk_if_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    expression: &mut IfExpression,
+) {
+    visitor.visit_expression_mut(&mut expression.condition);
+    visitor.visit_block_mut(&mut expression.consequence);
+
+    if let Some(alternative) = &mut expression.alternative {
+        visitor.visit_block_mut(alternative);
+    }
+}
+
+pub fn walk_function_literal_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    function: &mut FunctionLiteral,
+) {
+    for parameter in &mut function.parameters {
+        visitor.visit_binding_mut(parameter);
+    }
+
+    visitor.visit_block_mut(&mut function.body);
+}
+
+pub fn walk_call_expression_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    call: &mut CallExpression,
+) {
+    visitor.visit_expression_mut(&mut call.function);
+
+    for argument in &mut call.arguments {
+        visitor.visit_expression_mut(argument);
+    }
+}
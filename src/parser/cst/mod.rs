@@ -0,0 +1,127 @@
+pub mod typed;
+
+use crate::lexer::token::{Span, Token, TokenType};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    Root,
+    LetStatement,
+    AssignStatement,
+    ReturnStatement,
+    ExpressionStatement,
+    Name,
+    Literal,
+    PrefixExpression,
+    InfixExpression,
+    ParenExpression,
+    IfExpression,
+    Block,
+    FunctionLiteral,
+    ParameterList,
+    CallExpression,
+    ArgumentList,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(Token),
+}
+
+// a node of the concrete syntax tree. it holds every token it was parsed from,
+// whitespace and comments included, so printing the root gives back the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|x| match x {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    // the tokens directly under this node, without trivia
+    pub fn tokens(&self) -> impl Iterator<Item = &Token> {
+        self.children.iter().filter_map(|x| match x {
+            SyntaxElement::Token(token) if !is_trivia(token) => Some(token),
+            _ => None,
+        })
+    }
+
+    pub fn token(&self, token_type: TokenType) -> Option<&Token> {
+        self.tokens().find(|x| x.token_type == token_type)
+    }
+
+    // every token under this node in source order, trivia included
+    pub fn descendant_tokens(&self) -> Vec<&Token> {
+        let mut tokens = Vec::new();
+
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.descendant_tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+
+        tokens
+    }
+
+    // from the first to the last token that is not trivia
+    pub fn span(&self) -> Option<Span> {
+        let tokens = self.descendant_tokens();
+        let mut tokens = tokens.iter().filter(|x| !is_trivia(x));
+
+        let first = tokens.next()?.span;
+        let last = tokens.next_back().map_or(first, |x| x.span);
+        Some(first.to(&last))
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for token in self.descendant_tokens() {
+            write!(f, "{}", token.literal)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn is_trivia(token: &Token) -> bool {
+    matches!(token.token_type, TokenType::WHITESPACE | TokenType::COMMENT)
+}
+
+// collects tokens as the parser consumes them. a node is made once its last
+// token is in, by wrapping everything from where it started
+#[derive(Debug, Default)]
+pub struct Builder {
+    elements: Vec<SyntaxElement>,
+}
+
+impl Builder {
+    pub fn token(&mut self, token: Token) {
+        self.elements.push(SyntaxElement::Token(token));
+    }
+
+    pub fn checkpoint(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn finish_node(&mut self, start: usize, kind: SyntaxKind) {
+        let children = self.elements.split_off(start.min(self.elements.len()));
+        self.elements
+            .push(SyntaxElement::Node(SyntaxNode { kind, children }));
+    }
+
+    pub fn finish(self) -> SyntaxNode {
+        SyntaxNode {
+            kind: SyntaxKind::Root,
+            children: self.elements,
+        }
+    }
+}
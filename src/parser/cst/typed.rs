@@ -0,0 +1,317 @@
+use super::{SyntaxKind, SyntaxNode};
+use crate::lexer::token::{Token, TokenType};
+
+// typed views over the nodes of a syntax tree. they only look things up in
+// the tree, so every accessor returns None for parts missing from broken code
+
+#[derive(Debug, Clone, Copy)]
+pub struct Root<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> Root<'a> {
+    pub fn cast(syntax: &'a SyntaxNode) -> Option<Root<'a>> {
+        match syntax.kind {
+            SyntaxKind::Root => Some(Root { syntax }),
+            _ => None,
+        }
+    }
+
+    pub fn statements(&self) -> Vec<Statement<'a>> {
+        statements(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Statement<'a> {
+    Let(LetStatement<'a>),
+    Assign(AssignStatement<'a>),
+    Return(ReturnStatement<'a>),
+    Expression(ExpressionStatement<'a>),
+}
+
+impl<'a> Statement<'a> {
+    pub fn cast(syntax: &'a SyntaxNode) -> Option<Statement<'a>> {
+        match syntax.kind {
+            SyntaxKind::LetStatement => Some(Statement::Let(LetStatement { syntax })),
+            SyntaxKind::AssignStatement => Some(Statement::Assign(AssignStatement { syntax })),
+            SyntaxKind::ReturnStatement => Some(Statement::Return(ReturnStatement { syntax })),
+            SyntaxKind::ExpressionStatement => {
+                Some(Statement::Expression(ExpressionStatement { syntax }))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn syntax(&self) -> &'a SyntaxNode {
+        match self {
+            Statement::Let(x) => x.syntax,
+            Statement::Assign(x) => x.syntax,
+            Statement::Return(x) => x.syntax,
+            Statement::Expression(x) => x.syntax,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LetStatement<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> LetStatement<'a> {
+    pub fn name(&self) -> Option<&'a Token> {
+        self.syntax.token(TokenType::IDENT)
+    }
+
+    pub fn is_const(&self) -> bool {
+        self.syntax.token(TokenType::CONST).is_some()
+    }
+
+    pub fn value(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AssignStatement<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> AssignStatement<'a> {
+    pub fn name(&self) -> Option<&'a Token> {
+        self.syntax.token(TokenType::IDENT)
+    }
+
+    pub fn operator(&self) -> Option<&'a Token> {
+        self.syntax.tokens().nth(1)
+    }
+
+    pub fn value(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReturnStatement<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> ReturnStatement<'a> {
+    pub fn value(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExpressionStatement<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> ExpressionStatement<'a> {
+    pub fn expression(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+
+    pub fn has_semicolon(&self) -> bool {
+        self.syntax.token(TokenType::SEMICOLON).is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Expr<'a> {
+    Name(Name<'a>),
+    Literal(Literal<'a>),
+    Prefix(PrefixExpression<'a>),
+    Infix(InfixExpression<'a>),
+    Paren(ParenExpression<'a>),
+    If(IfExpression<'a>),
+    Function(FunctionLiteral<'a>),
+    Call(CallExpression<'a>),
+}
+
+impl<'a> Expr<'a> {
+    pub fn cast(syntax: &'a SyntaxNode) -> Option<Expr<'a>> {
+        let expression = match syntax.kind {
+            SyntaxKind::Name => Expr::Name(Name { syntax }),
+            SyntaxKind::Literal => Expr::Literal(Literal { syntax }),
+            SyntaxKind::PrefixExpression => Expr::Prefix(PrefixExpression { syntax }),
+            SyntaxKind::InfixExpression => Expr::Infix(InfixExpression { syntax }),
+            SyntaxKind::ParenExpression => Expr::Paren(ParenExpression { syntax }),
+            SyntaxKind::IfExpression => Expr::If(IfExpression { syntax }),
+            SyntaxKind::FunctionLiteral => Expr::Function(FunctionLiteral { syntax }),
+            SyntaxKind::CallExpression => Expr::Call(CallExpression { syntax }),
+            _ => return None,
+        };
+
+        Some(expression)
+    }
+
+    pub fn syntax(&self) -> &'a SyntaxNode {
+        match self {
+            Expr::Name(x) => x.syntax,
+            Expr::Literal(x) => x.syntax,
+            Expr::Prefix(x) => x.syntax,
+            Expr::Infix(x) => x.syntax,
+            Expr::Paren(x) => x.syntax,
+            Expr::If(x) => x.syntax,
+            Expr::Function(x) => x.syntax,
+            Expr::Call(x) => x.syntax,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Name<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> Name<'a> {
+    pub fn token(&self) -> Option<&'a Token> {
+        self.syntax.tokens().next()
+    }
+}
+
+// an integer or a boolean
+#[derive(Debug, Clone, Copy)]
+pub struct Literal<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> Literal<'a> {
+    pub fn token(&self) -> Option<&'a Token> {
+        self.syntax.tokens().next()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PrefixExpression<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> PrefixExpression<'a> {
+    pub fn operator(&self) -> Option<&'a Token> {
+        self.syntax.tokens().next()
+    }
+
+    pub fn operand(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InfixExpression<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> InfixExpression<'a> {
+    pub fn left(&self) -> Option<Expr<'a>> {
+        self.syntax.nodes().next().and_then(Expr::cast)
+    }
+
+    pub fn operator(&self) -> Option<&'a Token> {
+        self.syntax.tokens().next()
+    }
+
+    pub fn right(&self) -> Option<Expr<'a>> {
+        self.syntax.nodes().nth(1).and_then(Expr::cast)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParenExpression<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> ParenExpression<'a> {
+    pub fn inner(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IfExpression<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> IfExpression<'a> {
+    pub fn condition(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+
+    pub fn consequence(&self) -> Option<Block<'a>> {
+        self.blocks().next()
+    }
+
+    pub fn alternative(&self) -> Option<Block<'a>> {
+        self.blocks().nth(1)
+    }
+
+    fn blocks(&self) -> impl Iterator<Item = Block<'a>> {
+        self.syntax
+            .nodes()
+            .filter(|x| x.kind == SyntaxKind::Block)
+            .map(|syntax| Block { syntax })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Block<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> Block<'a> {
+    pub fn statements(&self) -> Vec<Statement<'a>> {
+        statements(self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FunctionLiteral<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> FunctionLiteral<'a> {
+    pub fn parameters(&self) -> Vec<Name<'a>> {
+        self.syntax
+            .nodes()
+            .filter(|x| x.kind == SyntaxKind::ParameterList)
+            .flat_map(|x| x.nodes())
+            .map(|syntax| Name { syntax })
+            .collect()
+    }
+
+    pub fn body(&self) -> Option<Block<'a>> {
+        self.syntax
+            .nodes()
+            .find(|x| x.kind == SyntaxKind::Block)
+            .map(|syntax| Block { syntax })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CallExpression<'a> {
+    pub syntax: &'a SyntaxNode,
+}
+
+impl<'a> CallExpression<'a> {
+    pub fn function(&self) -> Option<Expr<'a>> {
+        first_expression(self.syntax)
+    }
+
+    pub fn arguments(&self) -> Vec<Expr<'a>> {
+        self.syntax
+            .nodes()
+            .filter(|x| x.kind == SyntaxKind::ArgumentList)
+            .flat_map(|x| x.nodes())
+            .filter_map(Expr::cast)
+            .collect()
+    }
+}
+
+fn statements(syntax: &SyntaxNode) -> Vec<Statement<'_>> {
+    syntax.nodes().filter_map(Statement::cast).collect()
+}
+
+fn first_expression(syntax: &SyntaxNode) -> Option<Expr<'_>> {
+    syntax.nodes().find_map(Expr::cast)
+}
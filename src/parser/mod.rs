@@ -1,23 +1,82 @@
 pub mod ast;
+pub mod cst;
 
 use self::ast::{expressions::*, statements::*, *};
+use self::cst::{is_trivia, Builder, SyntaxKind, SyntaxNode};
+use super::diagnostics::Diagnostic;
 use super::lexer::{token::*, *};
+use super::object::BigInt;
+use super::utils::suggest;
+
+const UNEXPECTED_TOKEN: &str = "E0001";
+const NO_PREFIX_PARSE_FUNCTION: &str = "E0002";
+const INVALID_INTEGER: &str = "E0003";
+const MISSPELT_KEYWORD: &str = "E0004";
+const EXPRESSION_TOO_DEEP: &str = "E0005";
+
+// expressions nest at most this deep, counting each operator applied to a
+// chain of them, so that every pass over the tree fits on the native stack
+pub const MAX_EXPRESSION_DEPTH: usize = 1_000;
+
+// parses a whole source, failing with every problem found
+pub fn parse(input: &str) -> Result<Program, Vec<Diagnostic>> {
+    let lexer = Lexer::new(&input);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+
+    if parser.errors().is_empty() {
+        Ok(program)
+    } else {
+        Err(parser.errors().to_vec())
+    }
+}
+
+// like parse, but keeps every token, whitespace and comment in a syntax tree.
+// the tree is built even when there are errors
+pub fn parse_lossless(input: &str) -> (SyntaxNode, Vec<Diagnostic>) {
+    let lexer = Lexer::new(&input).with_trivia();
+    let mut parser = Parser::new(lexer);
+    parser.parse_program();
+
+    let tree = parser.syntax_tree().expect("lexer keeps trivia");
+    (tree, parser.errors)
+}
 
 #[derive(Debug)]
 pub struct Parser {
     lexer: Lexer,
     current_token: Token,
     peek_token: Token,
+    errors: Vec<Diagnostic>,
+    // whitespace and comments read before peek_token
+    trivia: Vec<Token>,
+    // only built when the lexer keeps trivia
+    syntax: Option<Builder>,
+    // where the node starting at current_token begins in the syntax tree
+    current_start: usize,
+    // how many expressions the current one is nested in
+    depth: usize,
+    // set once an expression nests too deep, after which the input is skipped
+    too_deep: bool,
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialOrd, PartialEq, Ord, Eq)]
 enum Precedence {
     LOWEST,
+    COALESCE,
+    LOGICALOR,
+    LOGICALAND,
     EQUALS,
     LESSGREATER,
+    BITOR,
+    BITXOR,
+    BITAND,
+    SHIFT,
     SUM,
     PRODUCT,
     PREFIX,
+    EXPONENT,
     CALL,
 }
 
@@ -27,14 +86,26 @@ impl Precedence {
         use super::lexer::token::TokenType::*;
 
         match token {
+            NULLISH => COALESCE,
+            OR => LOGICALOR,
+            AND => LOGICALAND,
             EQ => EQUALS,
             NOTEQ => EQUALS,
             GT => LESSGREATER,
             LT => LESSGREATER,
+            GTEQ => LESSGREATER,
+            LTEQ => LESSGREATER,
+            PIPE => BITOR,
+            CARET => BITXOR,
+            AMPERSAND => BITAND,
+            SHIFTLEFT => SHIFT,
+            SHIFTRIGHT => SHIFT,
             PLUS => SUM,
             MINUS => SUM,
             SLASH => PRODUCT,
             ASTERISK => PRODUCT,
+            PERCENT => PRODUCT,
+            POWER => EXPONENT,
             LPAREN => CALL,
             _ => LOWEST,
         }
@@ -43,26 +114,49 @@ impl Precedence {
 
 impl Parser {
     pub fn new(lexer: Lexer) -> Self {
+        let syntax = if lexer.has_trivia() {
+            Some(Builder::default())
+        } else {
+            None
+        };
+
         let mut parser = Parser {
             lexer,
             current_token: Token::new(TokenType::ILLEGAL, &""),
             peek_token: Token::new(TokenType::ILLEGAL, &""),
+            errors: Vec::new(),
+            trivia: Vec::new(),
+            syntax,
+            current_start: 0,
+            depth: 0,
+            too_deep: false,
         };
 
-        parser.next_token();
+        parser.peek_token = parser.read_token();
         parser.next_token();
 
         parser
     }
 
+    // the concrete syntax tree of what parse_program read, when the lexer keeps trivia
+    pub fn syntax_tree(&mut self) -> Option<SyntaxNode> {
+        self.syntax.take().map(|x| x.finish())
+    }
+
+    // problems found by parse_program. the program should not be evaluated unless this is empty
+    pub fn errors(&self) -> &[Diagnostic] {
+        &self.errors
+    }
+
     pub fn parse_program(&mut self) -> Program {
         let mut program = Program {
             statements: Vec::new(),
         };
 
         while self.current_token.token_type != TokenType::EOF {
-            let stmt = self.parse_statement();
-            program.statements.push(stmt);
+            if let Some(stmt) = self.parse_statement() {
+                program.statements.push(stmt);
+            }
 
             self.next_token();
         }
@@ -70,28 +164,68 @@ impl Parser {
         program
     }
 
-    fn parse_statement(&mut self) -> Statements {
+    fn parse_statement(&mut self) -> Option<Statements> {
         match self.current_token.token_type {
-            TokenType::LET => self.parse_let_statement(),
-            TokenType::RETURN => self.parse_return_statement(),
-            _ => self.parse_expression_statement(),
+            TokenType::LET | TokenType::CONST => self.parse_let_statement(),
+            TokenType::IDENT if self.peek_token_is_assignment() => {
+                Some(self.parse_assign_statement())
+            }
+            TokenType::RETURN => Some(self.parse_return_statement()),
+            _ => Some(self.parse_expression_statement()),
         }
     }
 
-    fn parse_let_statement(&mut self) -> Statements {
+    fn parse_let_statement(&mut self) -> Option<Statements> {
+        let start = self.current_start;
         let token = self.current_token.clone();
 
-        // FIXME to notify errors messegage
-        assert!(self.peek_token_is(&TokenType::IDENT));
+        if !self.expect_peek_token(&TokenType::IDENT) {
+            return None;
+        }
+
+        let name = Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        };
+
+        if !self.expect_peek_token(&TokenType::ASSIGN) {
+            return None;
+        }
         self.next_token();
 
+        let value = self.parse_expression(&Precedence::LOWEST);
+
+        if self.peek_token_is(&TokenType::SEMICOLON) {
+            self.next_token();
+        }
+        self.finish_node(start, SyntaxKind::LetStatement);
+
+        Some(Statements::LetStatement(LetStatement {
+            token,
+            name,
+            value,
+        }))
+    }
+
+    fn peek_token_is_assignment(&self) -> bool {
+        use super::lexer::token::TokenType::*;
+
+        matches!(
+            self.peek_token.token_type,
+            ASSIGN | PLUSASSIGN | MINUSASSIGN | ASTERISKASSIGN | SLASHASSIGN
+        )
+    }
+
+    fn parse_assign_statement(&mut self) -> Statements {
+        let start = self.current_start;
         let name = Identifier {
             token: self.current_token.clone(),
             value: self.current_token.literal.clone(),
         };
 
-        // FIXME to notify errors messegage
-        self.expect_peek_token(&TokenType::ASSIGN);
+        self.next_token();
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
         self.next_token();
 
         let value = self.parse_expression(&Precedence::LOWEST);
@@ -99,20 +233,28 @@ impl Parser {
         if self.peek_token_is(&TokenType::SEMICOLON) {
             self.next_token();
         }
+        self.finish_node(start, SyntaxKind::AssignStatement);
 
-        Statements::LetStatement(LetStatement { token, name, value })
+        Statements::AssignStatement(AssignStatement {
+            token,
+            name,
+            operator,
+            value,
+        })
     }
 
     fn parse_return_statement(&mut self) -> Statements {
+        let start = self.current_start;
         let token = self.current_token.clone();
 
         self.next_token();
 
         let return_value = self.parse_expression(&Precedence::LOWEST);
 
-        while !self.current_token_is(&TokenType::SEMICOLON) {
+        if self.peek_token_is(&TokenType::SEMICOLON) {
             self.next_token();
         }
+        self.finish_node(start, SyntaxKind::ReturnStatement);
 
         Statements::ReturnStatement(ReturnStatement {
             token,
@@ -121,57 +263,134 @@ impl Parser {
     }
 
     fn parse_expression_statement(&mut self) -> Statements {
+        let start = self.current_start;
         let stmt = ExpressionStatement {
             token: self.current_token.clone(),
             expression: self.parse_expression(&Precedence::LOWEST),
         };
 
+        if let Expression::Identifier(ref identifier) = stmt.expression {
+            self.check_misspelt_keyword(identifier);
+        }
+
         if self.peek_token_is(&TokenType::SEMICOLON) {
             self.next_token();
         }
+        self.finish_node(start, SyntaxKind::ExpressionStatement);
 
         Statements::ExpressionStatement(stmt)
     }
 
+    // an identifier directly followed by more of the statement, as in `retrun x;`,
+    // is most likely a misspelt keyword
+    fn check_misspelt_keyword(&mut self, identifier: &Identifier) {
+        use super::lexer::token::TokenType::*;
+
+        if matches!(self.peek_token.token_type, SEMICOLON | RBRACE | EOF)
+            || self.peek_token.span.line != identifier.token.span.line
+        {
+            return;
+        }
+
+        let keyword = match suggest(&identifier.value, KEYWORDS.iter().map(|&(x, _)| x)) {
+            Some(x) => x,
+            None => return,
+        };
+
+        let diagnostic = Diagnostic::error(
+            MISSPELT_KEYWORD,
+            format!("unexpected identifier `{}`", identifier.value),
+        )
+        .with_primary(identifier.token.span, "not a keyword")
+        .with_help(format!("did you mean `{}`?", keyword));
+        self.errors.push(diagnostic);
+
+        while !matches!(self.peek_token.token_type, SEMICOLON | RBRACE | EOF) {
+            self.next_token();
+        }
+    }
+
     fn parse_expression(&mut self, preceduce: &Precedence) -> Expression {
+        if self.depth >= MAX_EXPRESSION_DEPTH {
+            self.too_deep_error();
+            return Expression::ILLEGAL;
+        }
+
+        let depth = self.depth;
+        self.depth += 1;
+
+        let start = self.current_start;
         let token = self.current_token.clone();
-        let mut left = self
-            .parse_prefix(&token.token_type)
-            .expect("failt parse_expression");
+        let mut left = match self.parse_prefix(&token.token_type) {
+            Some(x) => x,
+            None => {
+                self.depth = depth;
+                return Expression::ILLEGAL;
+            }
+        };
 
         while !self.peek_token_is(&TokenType::SEMICOLON) && self.peek_precedence() > *preceduce {
             let token = self.peek_token.clone();
 
             self.next_token();
+
+            // each operator wraps the expression so far one level deeper
+            if self.depth >= MAX_EXPRESSION_DEPTH {
+                self.too_deep_error();
+                break;
+            }
+            self.depth += 1;
+
             left = self.parse_infix(&token.token_type, left);
+
+            let kind = match token.token_type {
+                TokenType::LPAREN => SyntaxKind::CallExpression,
+                _ => SyntaxKind::InfixExpression,
+            };
+            self.finish_node(start, kind);
         }
 
+        self.depth = depth;
         left
     }
 
     fn parse_prefix(&mut self, token_type: &TokenType) -> Option<Expression> {
         use super::lexer::token::TokenType::*;
 
-        match token_type {
-            IDENT => Some(self.parse_identifier()),
-            INT => Some(self.parse_integer_literal()),
-            BANG | MINUS => Some(self.parse_prefix_expression()),
-            TRUE | FALSE => Some(self.parse_boolean()),
-            LPAREN => self.parse_group_expression(),
-            IF => self.parse_if_expression(),
-            FUNCTION => self.parse_function_literal(),
-            _ => None,
+        let start = self.current_start;
+        let (expression, kind) = match token_type {
+            IDENT => (Some(self.parse_identifier()), SyntaxKind::Name),
+            INT => (Some(self.parse_integer_literal()), SyntaxKind::Literal),
+            BANG | MINUS | TILDE => (
+                Some(self.parse_prefix_expression()),
+                SyntaxKind::PrefixExpression,
+            ),
+            TRUE | FALSE => (Some(self.parse_boolean()), SyntaxKind::Literal),
+            NULL => (Some(self.parse_null()), SyntaxKind::Literal),
+            LPAREN => (self.parse_group_expression(), SyntaxKind::ParenExpression),
+            IF => (self.parse_if_expression(), SyntaxKind::IfExpression),
+            FUNCTION => (self.parse_function_literal(), SyntaxKind::FunctionLiteral),
+            _ => {
+                self.no_prefix_parse_error();
+                return None;
+            }
+        };
+
+        if expression.is_some() {
+            self.finish_node(start, kind);
         }
+        expression
     }
 
     fn parse_infix(&mut self, token_type: &TokenType, left: Expression) -> Expression {
         use super::lexer::token::TokenType::*;
 
         match token_type {
-            PLUS | MINUS | SLASH | ASTERISK | EQ | NOTEQ | LT | GT => {
+            PLUS | MINUS | SLASH | ASTERISK | PERCENT | POWER | AMPERSAND | PIPE | CARET
+            | SHIFTLEFT | SHIFTRIGHT | EQ | NOTEQ | LT | GT | LTEQ | GTEQ | AND | OR | NULLISH => {
                 self.parse_infix_expression(left)
             }
-            LPAREN => self.parse_call_expression(Expression::default()),
+            LPAREN => self.parse_call_expression(left),
             _ => left,
         }
     }
@@ -180,7 +399,11 @@ impl Parser {
         let token = self.current_token.clone();
         let operator = self.current_token.literal.clone();
         let left = Box::new(left);
-        let precedence = self.current_precedence();
+        // `**` is right associative, so its right side takes in another `**`
+        let precedence = match self.current_precedence() {
+            Precedence::EXPONENT => Precedence::PREFIX,
+            x => x,
+        };
 
         self.next_token();
 
@@ -201,11 +424,27 @@ impl Parser {
         })
     }
 
-    fn parse_integer_literal(&self) -> Expression {
-        Expression::IntegerLiteral(IntegerLiteral {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.parse().unwrap(),
-        })
+    // literals beyond 64 bits become big integers
+    fn parse_integer_literal(&mut self) -> Expression {
+        let token = self.current_token.clone();
+
+        if let Ok(value) = token.literal.parse() {
+            return Expression::IntegerLiteral(IntegerLiteral { token, value });
+        }
+
+        match BigInt::parse(&token.literal) {
+            Some(value) => Expression::BigIntLiteral(BigIntLiteral { token, value }),
+            None => {
+                let diagnostic = Diagnostic::error(
+                    INVALID_INTEGER,
+                    format!("could not parse {} as integer", token.literal),
+                )
+                .with_primary(token.span, "not an integer literal");
+                self.errors.push(diagnostic);
+
+                Expression::IntegerLiteral(IntegerLiteral { token, value: 0 })
+            }
+        }
     }
 
     fn parse_boolean(&self) -> Expression {
@@ -215,6 +454,12 @@ impl Parser {
         })
     }
 
+    fn parse_null(&self) -> Expression {
+        Expression::NullLiteral(NullLiteral {
+            token: self.current_token.clone(),
+        })
+    }
+
     fn parse_prefix_expression(&mut self) -> Expression {
         let token = self.current_token.clone();
         let operator = self.current_token.literal.clone();
@@ -286,16 +531,19 @@ impl Parser {
     }
 
     fn parse_block_statement(&mut self) -> BlockStatement {
+        let start = self.current_start;
         let token = self.current_token.clone();
         self.next_token();
 
         let mut statements = Vec::new();
         while !self.current_token_is(&TokenType::RBRACE) && !self.current_token_is(&TokenType::EOF)
         {
-            let stmt = self.parse_statement();
-            statements.push(stmt);
+            if let Some(stmt) = self.parse_statement() {
+                statements.push(stmt);
+            }
             self.next_token();
         }
+        self.finish_node(start, SyntaxKind::Block);
 
         BlockStatement { token, statements }
     }
@@ -307,10 +555,7 @@ impl Parser {
             return None;
         };
 
-        let parameters = match self.parse_function_parameters() {
-            Some(x) => x,
-            None => return None,
-        };
+        let parameters = self.parse_function_parameters()?;
 
         if !self.expect_peek_token(&TokenType::LBRACE) {
             return None;
@@ -326,38 +571,41 @@ impl Parser {
     }
 
     fn parse_function_parameters(&mut self) -> Option<Vec<Identifier>> {
+        let start = self.current_start;
         let mut identifiers = Vec::new();
 
         if self.peek_token_is(&TokenType::RPAREN) {
             self.next_token();
+            self.finish_node(start, SyntaxKind::ParameterList);
             return Some(identifiers);
         };
 
         self.next_token();
-
-        identifiers.push(Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        });
+        identifiers.push(self.parse_parameter());
 
         while self.peek_token_is(&TokenType::COMMA) {
             self.next_token();
             self.next_token();
-            identifiers.push(Identifier {
-                token: self.current_token.clone(),
-                value: self.current_token.literal.clone(),
-            });
+            identifiers.push(self.parse_parameter());
         }
 
         if !self.expect_peek_token(&TokenType::RPAREN) {
-            println!("{:?}", self.current_token);
-            println!("{:?}", self.peek_token);
             return None;
         }
+        self.finish_node(start, SyntaxKind::ParameterList);
 
         Some(identifiers)
     }
 
+    fn parse_parameter(&mut self) -> Identifier {
+        self.finish_node(self.current_start, SyntaxKind::Name);
+
+        Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+        }
+    }
+
     fn parse_call_expression(&mut self, function: Expression) -> Expression {
         let arguments = self.parse_call_arguments();
 
@@ -369,10 +617,12 @@ impl Parser {
     }
 
     fn parse_call_arguments(&mut self) -> Vec<Expression> {
+        let start = self.current_start;
         let mut arguments = Vec::new();
 
         if self.peek_token_is(&TokenType::RPAREN) {
             self.next_token();
+            self.finish_node(start, SyntaxKind::ArgumentList);
             return arguments;
         };
 
@@ -385,9 +635,8 @@ impl Parser {
             arguments.push(self.parse_expression(&Precedence::LOWEST));
         }
 
-        if !self.expect_peek_token(&TokenType::RPAREN) {
-            panic!("expected: ), but get {}", self.peek_token.literal)
-        }
+        self.expect_peek_token(&TokenType::RPAREN);
+        self.finish_node(start, SyntaxKind::ArgumentList);
 
         arguments
     }
@@ -409,16 +658,102 @@ impl Parser {
     }
 
     fn next_token(&mut self) {
-        self.current_token = self.peek_token.clone();
-        self.peek_token = self.lexer.next_token();
+        let trivia = std::mem::take(&mut self.trivia);
+        let token = self.read_token();
+        self.current_token = std::mem::replace(&mut self.peek_token, token);
+
+        if let Some(syntax) = &mut self.syntax {
+            for x in trivia {
+                syntax.token(x);
+            }
+
+            self.current_start = syntax.checkpoint();
+            if self.current_token.token_type != TokenType::EOF {
+                syntax.token(self.current_token.clone());
+            }
+        }
+    }
+
+    // the next token that is not whitespace or a comment
+    fn read_token(&mut self) -> Token {
+        loop {
+            let token = self.lexer.next_token();
+            if !is_trivia(&token) {
+                return token;
+            }
+            self.trivia.push(token);
+        }
+    }
+
+    // wraps everything read since start into a node, once the node's last token is current
+    fn finish_node(&mut self, start: usize, kind: SyntaxKind) {
+        if let Some(syntax) = &mut self.syntax {
+            syntax.finish_node(start, kind);
+        }
     }
 
     fn expect_peek_token(&mut self, token_type: &TokenType) -> bool {
-        if self.peek_token_is(&token_type) {
+        if self.peek_token_is(token_type) {
             self.next_token();
             return true;
         }
 
+        self.peek_error(token_type);
         false
     }
+
+    fn peek_error(&mut self, token_type: &TokenType) {
+        if self.too_deep {
+            return;
+        }
+
+        let diagnostic = Diagnostic::error(
+            UNEXPECTED_TOKEN,
+            format!(
+                "expected next token to be {:?}, got {:?} instead",
+                token_type, self.peek_token.token_type
+            ),
+        )
+        .with_primary(self.peek_token.span, format!("expected {:?}", token_type));
+
+        self.errors.push(diagnostic);
+    }
+
+    fn no_prefix_parse_error(&mut self) {
+        if self.too_deep {
+            return;
+        }
+
+        let diagnostic = Diagnostic::error(
+            NO_PREFIX_PARSE_FUNCTION,
+            format!(
+                "no prefix parse function for {:?} found",
+                self.current_token.token_type
+            ),
+        )
+        .with_primary(self.current_token.span, "expected an expression");
+
+        self.errors.push(diagnostic);
+    }
+
+    // the rest of the input is skipped, as are the errors the expressions
+    // left unfinished would report
+    fn too_deep_error(&mut self) {
+        if self.too_deep {
+            return;
+        }
+        self.too_deep = true;
+
+        let diagnostic = Diagnostic::error(EXPRESSION_TOO_DEEP, "expression nested too deeply")
+            .with_primary(
+                self.current_token.span,
+                format!("nested more than {} deep", MAX_EXPRESSION_DEPTH),
+            )
+            .with_help("split the expression up with let bindings");
+        self.errors.push(diagnostic);
+
+        while !self.peek_token_is(&TokenType::EOF) {
+            self.next_token();
+        }
+    }
 }
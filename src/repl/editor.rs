@@ -0,0 +1,454 @@
+use super::highlight::highlight;
+use super::history::History;
+use crate::utils::{color_enabled, is_letter};
+use std::io::{stdin, stdout, Error, ErrorKind, IsTerminal, Read, Result, Write};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, PartialEq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    CtrlC,
+    CtrlD,
+    CtrlG,
+    CtrlR,
+    CtrlU,
+    Unknown,
+}
+
+// returns None once the input reaches EOF
+pub fn read_key(input: &mut impl Read) -> Result<Option<Key>> {
+    let byte = match read_byte(input)? {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        127 | 8 => Key::Backspace,
+        1 => Key::Home,
+        2 => Key::Left,
+        3 => Key::CtrlC,
+        4 => Key::CtrlD,
+        5 => Key::End,
+        6 => Key::Right,
+        7 => Key::CtrlG,
+        14 => Key::Down,
+        16 => Key::Up,
+        18 => Key::CtrlR,
+        21 => Key::CtrlU,
+        27 => read_escape_sequence(input)?,
+        x if x < 32 => Key::Unknown,
+        x => read_utf8_char(x, input)?,
+    };
+
+    Ok(Some(key))
+}
+
+fn read_byte(input: &mut impl Read) -> Result<Option<u8>> {
+    let mut buf = [0; 1];
+
+    match input.read(&mut buf)? {
+        0 => Ok(None),
+        _ => Ok(Some(buf[0])),
+    }
+}
+
+fn read_escape_sequence(input: &mut impl Read) -> Result<Key> {
+    match read_byte(input)? {
+        Some(b'[') | Some(b'O') => (),
+        _ => return Ok(Key::Unknown),
+    }
+
+    let mut parameter = String::new();
+    loop {
+        let byte = match read_byte(input)? {
+            Some(x) => x,
+            None => return Ok(Key::Unknown),
+        };
+
+        if byte.is_ascii_digit() || byte == b';' {
+            parameter.push(byte as char);
+            continue;
+        }
+
+        let key = match (byte, parameter.as_str()) {
+            (b'A', _) => Key::Up,
+            (b'B', _) => Key::Down,
+            (b'C', _) => Key::Right,
+            (b'D', _) => Key::Left,
+            (b'H', _) | (b'~', "1") | (b'~', "7") => Key::Home,
+            (b'F', _) | (b'~', "4") | (b'~', "8") => Key::End,
+            (b'~', "3") => Key::Delete,
+            _ => Key::Unknown,
+        };
+
+        return Ok(key);
+    }
+}
+
+fn read_utf8_char(first: u8, input: &mut impl Read) -> Result<Key> {
+    let width = match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    };
+
+    let mut bytes = vec![first];
+    for _ in 1..width {
+        match read_byte(input)? {
+            Some(x) => bytes.push(x),
+            None => return Ok(Key::Unknown),
+        }
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(x) => Ok(x.chars().next().map_or(Key::Unknown, Key::Char)),
+        Err(_) => Ok(Key::Unknown),
+    }
+}
+
+// candidates starting with `word`, sorted and without duplicates
+pub fn complete<'a>(word: &str, candidates: &'a [String]) -> Vec<&'a str> {
+    let mut matches: Vec<&str> = candidates
+        .iter()
+        .map(|x| x.as_str())
+        .filter(|x| x.starts_with(word))
+        .collect();
+
+    matches.sort_unstable();
+    matches.dedup();
+    matches
+}
+
+fn common_prefix(words: &[&str]) -> String {
+    let mut prefix = words.first().map_or(String::new(), |x| x.to_string());
+
+    for word in words {
+        while !word.starts_with(&prefix) {
+            prefix.pop();
+        }
+    }
+
+    prefix
+}
+
+pub struct Editor {
+    history: History,
+    color: bool,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Editor::new()
+    }
+}
+
+impl Editor {
+    pub fn new() -> Editor {
+        Editor {
+            history: History::load_default(),
+            color: color_enabled(&stdout()),
+        }
+    }
+
+    // reads a line with editing, history and completion of `candidates` when stdin is a terminal.
+    // returns None at EOF and an Interrupted error when the line is abandoned with Ctrl-C
+    pub fn read_line(&mut self, prompt: &str, candidates: &[String]) -> Result<Option<String>> {
+        if !stdin().is_terminal() {
+            return read_plain_line(prompt);
+        }
+
+        let raw_mode = match RawMode::enable() {
+            Some(x) => x,
+            None => return read_plain_line(prompt),
+        };
+
+        let line = self.edit(prompt, candidates);
+        drop(raw_mode);
+
+        if let Ok(Some(ref line)) = line {
+            // a history file that can't be written shouldn't stop the repl
+            self.history.push(line).ok();
+        }
+
+        line
+    }
+
+    fn edit(&mut self, prompt: &str, candidates: &[String]) -> Result<Option<String>> {
+        let mut input = stdin();
+        let mut out = stdout();
+        let mut line = Line::new(prompt, self.color);
+        let mut history_index = self.history.len();
+        let mut draft = String::new();
+
+        line.refresh(&mut out)?;
+
+        loop {
+            let key = match read_key(&mut input)? {
+                Some(x) => x,
+                None => return Ok(None),
+            };
+
+            match key {
+                Key::Enter => {
+                    write!(out, "\r\n")?;
+                    return Ok(Some(line.text()));
+                }
+                Key::CtrlD if line.buffer.is_empty() => {
+                    write!(out, "\r\n")?;
+                    return Ok(None);
+                }
+                Key::CtrlC => {
+                    write!(out, "^C\r\n")?;
+                    return Err(Error::new(ErrorKind::Interrupted, "interrupted"));
+                }
+                Key::Char(ch) => line.insert(ch),
+                Key::Backspace => line.backspace(),
+                Key::Delete | Key::CtrlD => line.delete(),
+                Key::Left => line.cursor = line.cursor.saturating_sub(1),
+                Key::Right => line.cursor = (line.cursor + 1).min(line.buffer.len()),
+                Key::Home => line.cursor = 0,
+                Key::End => line.cursor = line.buffer.len(),
+                Key::CtrlU => {
+                    line.buffer.drain(..line.cursor);
+                    line.cursor = 0;
+                }
+                Key::Up if history_index > 0 => {
+                    if history_index == self.history.len() {
+                        draft = line.text();
+                    }
+
+                    history_index -= 1;
+                    line.set(&self.history.entries()[history_index]);
+                }
+                Key::Down if history_index < self.history.len() => {
+                    history_index += 1;
+
+                    match self.history.get(history_index) {
+                        Some(entry) => line.set(entry),
+                        None => line.set(&draft),
+                    }
+                }
+                Key::Tab => self.complete(&mut line, candidates, &mut out)?,
+                Key::CtrlR => {
+                    if let Some(accepted) = self.reverse_search(&mut line, &mut input, &mut out)? {
+                        return Ok(Some(accepted));
+                    }
+                }
+                _ => (),
+            }
+
+            line.refresh(&mut out)?;
+        }
+    }
+
+    fn complete(&self, line: &mut Line, candidates: &[String], out: &mut impl Write) -> Result<()> {
+        let word = line.word_before_cursor();
+        if word.is_empty() {
+            return Ok(());
+        }
+
+        let matches = complete(&word, candidates);
+        let prefix = common_prefix(&matches);
+
+        if prefix.len() > word.len() {
+            prefix[word.len()..].chars().for_each(|ch| line.insert(ch));
+        } else if matches.len() > 1 {
+            write!(out, "\r\n{}\r\n", matches.join("  "))?;
+        }
+
+        Ok(())
+    }
+
+    // returns the matched entry when it is accepted with enter,
+    // otherwise leaves it in `line` to keep editing
+    fn reverse_search(
+        &self,
+        line: &mut Line,
+        input: &mut impl Read,
+        out: &mut impl Write,
+    ) -> Result<Option<String>> {
+        let original = line.text();
+        let mut query = String::new();
+        let mut found = None;
+
+        loop {
+            let matched = found
+                .and_then(|i| self.history.get(i))
+                .cloned()
+                .unwrap_or_default();
+
+            write!(out, "\r(reverse-i-search)`{}': {}\x1b[K", query, matched)?;
+            out.flush()?;
+
+            let key = match read_key(input)? {
+                Some(x) => x,
+                None => Key::CtrlG,
+            };
+
+            match key {
+                Key::Char(ch) => {
+                    query.push(ch);
+                    let before = found.map_or(self.history.len(), |i| i + 1);
+                    found = self.history.search(&query, before);
+                }
+                Key::Backspace => {
+                    query.pop();
+                    found = self.history.search(&query, self.history.len());
+                }
+                Key::CtrlR => {
+                    let before = found.unwrap_or_else(|| self.history.len());
+                    if let Some(i) = self.history.search(&query, before) {
+                        found = Some(i);
+                    }
+                }
+                Key::Enter => {
+                    line.set(&matched);
+                    line.refresh(out)?;
+                    write!(out, "\r\n")?;
+                    return Ok(Some(matched));
+                }
+                Key::CtrlG | Key::CtrlC => {
+                    line.set(&original);
+                    return Ok(None);
+                }
+                _ => {
+                    line.set(&matched);
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+struct Line<'a> {
+    prompt: &'a str,
+    buffer: Vec<char>,
+    cursor: usize,
+    color: bool,
+}
+
+impl<'a> Line<'a> {
+    fn new(prompt: &'a str, color: bool) -> Line<'a> {
+        Line {
+            prompt,
+            buffer: Vec::new(),
+            cursor: 0,
+            color,
+        }
+    }
+
+    fn text(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    fn set(&mut self, text: &str) {
+        self.buffer = text.chars().collect();
+        self.cursor = self.buffer.len();
+    }
+
+    fn insert(&mut self, ch: char) {
+        self.buffer.insert(self.cursor, ch);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.buffer.len() {
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    fn word_before_cursor(&self) -> String {
+        let start = self.buffer[..self.cursor]
+            .iter()
+            .rposition(|ch| !is_letter(*ch))
+            .map_or(0, |i| i + 1);
+
+        self.buffer[start..self.cursor].iter().collect()
+    }
+
+    fn refresh(&self, out: &mut impl Write) -> Result<()> {
+        let text = if self.color {
+            highlight(&self.text())
+        } else {
+            self.text()
+        };
+
+        write!(out, "\r{}{}\x1b[K", self.prompt, text)?;
+
+        let back = self.buffer.len() - self.cursor;
+        if back > 0 {
+            write!(out, "\x1b[{}D", back)?;
+        }
+
+        out.flush()
+    }
+}
+
+// switches the terminal into raw mode through stty and restores it when dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+
+        Some(RawMode {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout).ok()
+}
+
+fn read_plain_line(prompt: &str) -> Result<Option<String>> {
+    print!("{}", prompt);
+    stdout().flush()?;
+
+    let mut line = String::new();
+    match stdin().read_line(&mut line) {
+        Ok(0) => Ok(None),
+        Ok(_) => Ok(Some(line.trim().to_string())),
+        Err(e) => Err(e),
+    }
+}
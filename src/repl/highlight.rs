@@ -0,0 +1,75 @@
+use crate::lexer::token::TokenType::{self, *};
+use crate::lexer::Lexer;
+use crate::object::{Object, ObjectType};
+use crate::parser::ast::printer::print_function;
+
+const RESET: &str = "\x1b[0m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+const DIM: &str = "\x1b[2m";
+
+fn color_of(token_type: &TokenType) -> Option<&'static str> {
+    match token_type {
+        LET | CONST | FUNCTION | IF | ELSE | RETURN | TRUE | FALSE | NULL => Some(MAGENTA),
+        INT => Some(YELLOW),
+        ASSIGN | PLUSASSIGN | MINUSASSIGN | ASTERISKASSIGN | SLASHASSIGN | PLUS | MINUS | BANG
+        | ASTERISK | SLASH | PERCENT | POWER | AMPERSAND | PIPE | CARET | TILDE | SHIFTLEFT
+        | SHIFTRIGHT | EQ | NOTEQ | LT | GT | LTEQ | GTEQ | AND | OR | NULLISH => Some(CYAN),
+        ILLEGAL => Some(RED),
+        COMMENT => Some(DIM),
+        _ => None,
+    }
+}
+
+fn paint(text: &str, color: Option<&str>) -> String {
+    match color {
+        Some(color) => format!("{}{}{}", color, text, RESET),
+        None => text.to_string(),
+    }
+}
+
+// colours the tokens of `input`, keeping the whitespace between them as is
+pub fn highlight(input: &str) -> String {
+    let mut lexer = Lexer::new(&input).with_trivia();
+    let mut highlighted = String::new();
+
+    loop {
+        let token = lexer.next_token();
+        if token.token_type == EOF {
+            break;
+        }
+
+        highlighted.push_str(&paint(&token.literal, color_of(&token.token_type)));
+    }
+
+    highlighted
+}
+
+// like Object::inspect, but with function bodies laid out one statement per line
+pub fn pretty(object: &Object, color: bool) -> String {
+    let painted = |text: String, style| {
+        if color {
+            paint(&text, Some(style))
+        } else {
+            text
+        }
+    };
+
+    match object.object_type {
+        ObjectType::Integer(x) => painted(x.to_string(), YELLOW),
+        ObjectType::BigInt(ref x) => painted(x.to_string(), YELLOW),
+        ObjectType::Boolean(x) => painted(x.to_string(), MAGENTA),
+        ObjectType::Null => painted("null".to_string(), DIM),
+        ObjectType::ReturnValue(ref x) => pretty(x, color),
+        ObjectType::Function(ref function) => {
+            let text = print_function(&function.parameters, &function.body);
+            if color {
+                highlight(&text)
+            } else {
+                text
+            }
+        }
+    }
+}
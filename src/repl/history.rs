@@ -0,0 +1,99 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{Result, Write};
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = ".lolo_history";
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    // history kept in ~/.lolo_history, or in memory only when there is no home directory
+    pub fn load_default() -> History {
+        match env::var_os("HOME") {
+            Some(home) => History::load(PathBuf::from(home).join(HISTORY_FILE)),
+            None => History::default(),
+        }
+    }
+
+    pub fn load(path: PathBuf) -> History {
+        let entries = match fs::read_to_string(&path) {
+            Ok(x) => x.lines().map(|line| line.to_string()).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let mut history = History {
+            entries,
+            path: Some(path),
+        };
+
+        if history.len() > MAX_ENTRIES {
+            history.truncate();
+            history.save().ok();
+        }
+
+        history
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&String> {
+        self.entries.get(index)
+    }
+
+    // blank lines and repeats of the latest entry are not recorded
+    pub fn push(&mut self, line: &str) -> Result<()> {
+        if line.trim().is_empty() || self.entries.last().map(|x| x.as_str()) == Some(line) {
+            return Ok(());
+        }
+
+        self.entries.push(line.to_string());
+        self.truncate();
+
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+        }
+
+        Ok(())
+    }
+
+    // index of the newest entry before `before` that contains `query`
+    pub fn search(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(path) = &self.path {
+            let mut contents = self.entries.join("\n");
+            contents.push('\n');
+            fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+
+    fn truncate(&mut self) {
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+pub mod editor;
+pub mod highlight;
+pub mod history;
+
+use self::editor::Editor;
+use self::highlight::pretty;
+use super::diagnostics::Diagnostic;
+use super::evaluator::eval;
+use super::interrupt;
+use super::lexer::token::{TokenType, KEYWORDS};
+use super::lexer::Lexer;
+use super::object::Environment;
+use super::parser::parse;
+use super::utils::color_enabled;
+use std::cell::RefCell;
+use std::fs;
+use std::io::*;
+use std::rc::Rc;
+use std::string::*;
+use std::time::Instant;
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+const REPL_FILE_NAME: &str = "<repl>";
+
+const HELP: &str = ":tokens <expr>  show the tokens of <expr>
+:ast <expr>     show the parsed tree of <expr>
+:env            list the bindings of the session
+:load <file>    evaluate <file> into the session
+:time <expr>    evaluate <expr> and report how long it took
+:reset          clear all bindings of the session
+:help           show this message
+exit            quit the repl";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Tokens(String),
+    Ast(String),
+    Env,
+    Load(String),
+    Time(String),
+    Reset,
+    Help,
+    Unknown(String),
+}
+
+impl Command {
+    // returns None when the input is a program rather than a command
+    pub fn parse(input: &str) -> Option<Command> {
+        if !input.starts_with(':') {
+            return None;
+        }
+
+        let (name, argument) = match input.find(char::is_whitespace) {
+            Some(i) => (&input[..i], input[i..].trim().to_string()),
+            None => (input, String::new()),
+        };
+
+        let command = match name {
+            ":tokens" => Command::Tokens(argument),
+            ":ast" => Command::Ast(argument),
+            ":env" => Command::Env,
+            ":load" => Command::Load(argument),
+            ":time" => Command::Time(argument),
+            ":reset" => Command::Reset,
+            ":help" => Command::Help,
+            _ => Command::Unknown(name.to_string()),
+        };
+
+        Some(command)
+    }
+}
+
+pub fn run() -> Result<()> {
+    let mut env = Environment::new();
+    let mut editor = Editor::new();
+    interrupt::install_handler();
+
+    loop {
+        let input = match read_program(&mut editor, &env)? {
+            Some(x) => x,
+            None => {
+                println!();
+                break Ok(());
+            }
+        };
+
+        if input.is_empty() {
+            continue;
+        }
+
+        if input == "exit" {
+            break Ok(());
+        }
+
+        match Command::parse(&input) {
+            Some(command) => run_command(command, &mut env),
+            None => eval_input(&input, REPL_FILE_NAME, &env),
+        }
+    }
+}
+
+fn run_command(command: Command, env: &mut Rc<RefCell<Environment>>) {
+    match command {
+        Command::Tokens(input) => {
+            let mut lexer = Lexer::new(&input);
+
+            loop {
+                let token = lexer.next_token();
+                if token.token_type == TokenType::EOF {
+                    break;
+                }
+
+                println!("{:?} {:?}", token.token_type, token.literal);
+            }
+        }
+        Command::Ast(input) => match parse(&input) {
+            Ok(program) => {
+                for stmt in program.statements {
+                    println!("{}", stmt);
+                }
+            }
+            Err(errors) => print_diagnostics(&errors, &input, REPL_FILE_NAME),
+        },
+        Command::Env => {
+            for (name, value) in env.borrow().bindings() {
+                println!("{} = {}", name, value.inspect());
+            }
+        }
+        Command::Load(path) => match fs::read_to_string(&path) {
+            Ok(input) => eval_input(&input, &path, env),
+            Err(e) => println!("error: {}: {}", path, e),
+        },
+        Command::Time(input) => {
+            let start = Instant::now();
+            eval_input(&input, REPL_FILE_NAME, env);
+            println!("time: {:?}", start.elapsed());
+        }
+        Command::Reset => *env = Environment::new(),
+        Command::Help => println!("{}", HELP),
+        Command::Unknown(name) => println!("unknown command: {} (see :help)", name),
+    }
+}
+
+fn eval_input(input: &str, file_name: &str, env: &Rc<RefCell<Environment>>) {
+    let program = match parse(input) {
+        Ok(x) => x,
+        Err(errors) => return print_diagnostics(&errors, input, file_name),
+    };
+
+    interrupt::clear();
+    match eval(&program, env) {
+        Ok(evalted) => println!("{}", pretty(&evalted, color_enabled(&stdout()))),
+        Err(e) => print!("{}", e.render(input, file_name, color_enabled(&stdout()))),
+    }
+}
+
+fn print_diagnostics(diagnostics: &[Diagnostic], source: &str, file_name: &str) {
+    let color = color_enabled(&stdout());
+
+    for diagnostic in diagnostics {
+        print!("{}", diagnostic.render(source, file_name, color));
+    }
+}
+
+/// Returns false while the input still needs more lines to form a program,
+/// i.e. it has unclosed brackets or ends with an operator.
+pub fn is_complete(input: &str) -> bool {
+    use self::TokenType::*;
+
+    let mut lexer = Lexer::new(&input);
+    let mut depth = 0;
+    let mut last_token_type = EOF;
+
+    loop {
+        let token = lexer.next_token();
+
+        match token.token_type {
+            EOF => break,
+            LPAREN | LBRACE => depth += 1,
+            RPAREN | RBRACE => depth -= 1,
+            _ => (),
+        }
+
+        last_token_type = token.token_type;
+    }
+
+    if depth > 0 {
+        return false;
+    }
+
+    !matches!(
+        last_token_type,
+        ASSIGN
+            | PLUSASSIGN
+            | MINUSASSIGN
+            | ASTERISKASSIGN
+            | SLASHASSIGN
+            | PLUS
+            | MINUS
+            | BANG
+            | ASTERISK
+            | SLASH
+            | PERCENT
+            | POWER
+            | AMPERSAND
+            | PIPE
+            | CARET
+            | TILDE
+            | SHIFTLEFT
+            | SHIFTRIGHT
+            | EQ
+            | NOTEQ
+            | LT
+            | GT
+            | LTEQ
+            | GTEQ
+            | AND
+            | OR
+            | NULLISH
+            | COMMA
+    )
+}
+
+// returns None once stdin reaches EOF, and an empty program when the input is abandoned with Ctrl-C
+fn read_program(editor: &mut Editor, env: &Rc<RefCell<Environment>>) -> Result<Option<String>> {
+    let candidates = completion_candidates(env);
+    let mut input = String::new();
+    let mut prompt = PROMPT;
+
+    loop {
+        match editor.read_line(prompt, &candidates) {
+            Ok(Some(line)) => {
+                if !input.is_empty() {
+                    input.push('\n');
+                }
+                input.push_str(&line);
+            }
+            Ok(None) => return Ok(None),
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => return Ok(Some(String::new())),
+            Err(e) => return Err(e),
+        }
+
+        if is_complete(&input) {
+            return Ok(Some(input));
+        }
+
+        prompt = CONTINUATION_PROMPT;
+    }
+}
+
+fn completion_candidates(env: &Rc<RefCell<Environment>>) -> Vec<String> {
+    let mut candidates: Vec<String> = KEYWORDS.iter().map(|&(x, _)| x.to_string()).collect();
+
+    for (name, _) in env.borrow().bindings() {
+        candidates.push(name);
+    }
+
+    candidates
+}
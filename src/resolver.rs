@@ -0,0 +1,228 @@
+use crate::diagnostics::Diagnostic;
+use crate::lexer::token::{Span, KEYWORDS};
+use crate::parser::ast::expressions::*;
+use crate::parser::ast::statements::*;
+use crate::parser::ast::visit::*;
+use crate::parser::ast::Program;
+use crate::utils::suggest;
+use std::collections::{HashMap, HashSet};
+
+const UNDEFINED_NAME: &str = "E0200";
+const DUPLICATE_PARAMETER: &str = "E0201";
+const RETURN_OUTSIDE_FUNCTION: &str = "E0202";
+const ASSIGNMENT_TO_CONSTANT: &str = "E0203";
+const REDECLARED_CONSTANT: &str = "E0204";
+
+// checks a program for undefined names, duplicate parameters, stray returns
+// and changes to constants without running it
+pub fn resolve(program: &Program) -> Vec<Diagnostic> {
+    let mut resolver = Resolver::default();
+
+    resolver.begin_scope(false);
+    resolver.visit_program(program);
+    resolver.end_scope();
+
+    // function bodies are visited late, so restore source order
+    let mut diagnostics = resolver.diagnostics;
+    diagnostics.sort_by_key(|x| x.primary.as_ref().map(|label| label.span.start));
+    diagnostics
+}
+
+#[derive(Default)]
+struct Scope {
+    names: HashSet<String>,
+    // where each constant of the scope was declared
+    constants: HashMap<String, Span>,
+    is_function: bool,
+    // function bodies are resolved once the whole scope is known,
+    // since they may call functions bound after them
+    pending: Vec<FunctionLiteral>,
+}
+
+#[derive(Default)]
+struct Resolver {
+    scopes: Vec<Scope>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Resolver {
+    fn begin_scope(&mut self, is_function: bool) {
+        self.scopes.push(Scope {
+            is_function,
+            ..Scope::default()
+        });
+    }
+
+    fn end_scope(&mut self) {
+        let pending = std::mem::take(&mut self.current_scope().pending);
+        for function in &pending {
+            self.resolve_function_body(function);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn current_scope(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("resolver has no scope")
+    }
+
+    fn declare(&mut self, name: &str) {
+        self.current_scope().names.insert(name.to_string());
+    }
+
+    fn is_declared(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.names.contains(name))
+    }
+
+    // where the name was declared, if the binding it resolves to is a constant
+    fn constant(&self, name: &str) -> Option<Span> {
+        self.scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.names.contains(name))
+            .and_then(|scope| scope.constants.get(name).cloned())
+    }
+
+    fn in_function(&self) -> bool {
+        self.scopes.iter().any(|scope| scope.is_function)
+    }
+
+    fn resolve_identifier(&mut self, identifier: &Identifier) {
+        if self.is_declared(&identifier.value) {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::error(
+            UNDEFINED_NAME,
+            format!("cannot find `{}` in this scope", identifier.value),
+        )
+        .with_primary(identifier.token.span, "not found in this scope");
+
+        let names: Vec<&str> = self
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.names.iter().map(|x| x.as_str()))
+            .chain(KEYWORDS.iter().map(|&(x, _)| x))
+            .collect();
+        if let Some(suggestion) = suggest(&identifier.value, names) {
+            diagnostic = diagnostic.with_help(format!("did you mean `{}`?", suggestion));
+        }
+
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn check_assignment(&mut self, statement: &AssignStatement) {
+        let name = &statement.name;
+        let declaration = match self.constant(&name.value) {
+            Some(x) => x,
+            None => return,
+        };
+
+        let diagnostic = Diagnostic::error(
+            ASSIGNMENT_TO_CONSTANT,
+            format!("cannot assign to constant `{}`", name.value),
+        )
+        .with_primary(name.token.span, "cannot assign to a constant")
+        .with_secondary(declaration, "declared as constant here")
+        .with_help(format!(
+            "declare it with `let {} = ...` if it needs to change",
+            name.value
+        ));
+        self.diagnostics.push(diagnostic);
+    }
+
+    // a constant may be shadowed by a function scope, but not in its own
+    fn check_redeclaration(&mut self, identifier: &Identifier) {
+        let declaration = match self.current_scope().constants.get(&identifier.value) {
+            Some(x) => *x,
+            None => return,
+        };
+
+        let diagnostic = Diagnostic::error(
+            REDECLARED_CONSTANT,
+            format!(
+                "cannot redeclare constant `{}` in the same scope",
+                identifier.value
+            ),
+        )
+        .with_primary(identifier.token.span, "redeclared here")
+        .with_secondary(declaration, "declared as constant here");
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn check_parameters(&mut self, function: &FunctionLiteral) {
+        for (i, parameter) in function.parameters.iter().enumerate() {
+            let first = function.parameters[..i]
+                .iter()
+                .find(|x| x.value == parameter.value);
+
+            if let Some(first) = first {
+                let diagnostic = Diagnostic::error(
+                    DUPLICATE_PARAMETER,
+                    format!(
+                        "parameter `{}` is bound more than once in this parameter list",
+                        parameter.value
+                    ),
+                )
+                .with_primary(parameter.token.span, "used as parameter more than once")
+                .with_secondary(first.token.span, "first used here");
+                self.diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    fn resolve_function_body(&mut self, function: &FunctionLiteral) {
+        self.begin_scope(true);
+
+        for parameter in &function.parameters {
+            self.declare(&parameter.value);
+        }
+
+        walk_block(self, &function.body);
+        self.end_scope();
+    }
+}
+
+// blocks share the scope of the enclosing function, as they do when evaluated
+impl Visitor for Resolver {
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) {
+        if !self.in_function() {
+            let diagnostic =
+                Diagnostic::error(RETURN_OUTSIDE_FUNCTION, "return outside of a function")
+                    .with_primary(statement.token.span, "not inside a function");
+            self.diagnostics.push(diagnostic);
+        }
+
+        walk_return_statement(self, statement);
+    }
+
+    fn visit_let_statement(&mut self, statement: &LetStatement) {
+        walk_let_statement(self, statement);
+
+        if statement.is_const() {
+            let name = &statement.name;
+            self.current_scope()
+                .constants
+                .insert(name.value.clone(), name.token.span);
+        }
+    }
+
+    fn visit_assign_statement(&mut self, statement: &AssignStatement) {
+        walk_assign_statement(self, statement);
+        self.check_assignment(statement);
+    }
+
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        self.resolve_identifier(identifier);
+    }
+
+    fn visit_binding(&mut self, identifier: &Identifier) {
+        self.check_redeclaration(identifier);
+        self.declare(&identifier.value);
+    }
+
+    fn visit_function_literal(&mut self, function: &FunctionLiteral) {
+        self.check_parameters(function);
+        self.current_scope().pending.push(function.clone());
+    }
+}
@@ -1,7 +1,60 @@
+use crate::lexer::token::KEYWORDS;
+use std::env;
+use std::io::IsTerminal;
+
 pub fn is_letter(ch: char) -> bool {
-    'a' <= ch && ch <= 'z' || 'A' <= ch && ch <= 'Z' || ch == '|'
+    ch.is_ascii_alphabetic()
 }
 
 pub fn is_digit(ch: char) -> bool {
-    '0' <= ch && ch <= '9'
+    ch.is_ascii_digit()
+}
+
+// colours are only used on a terminal, and never when NO_COLOR is set
+pub fn color_enabled(stream: &impl IsTerminal) -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty());
+
+    stream.is_terminal() && !no_color
+}
+
+// levenshtein distance, counted in chars
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// the candidate closest to `name`, if it is close enough to be a likely typo.
+// on a tie a bound name wins over a keyword, then the first alphabetically
+pub fn suggest<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let max_distance = name.chars().count() / 3;
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|&(distance, candidate)| {
+            (
+                distance,
+                KEYWORDS.iter().any(|&(x, _)| x == candidate),
+                candidate,
+            )
+        })
+        .map(|(_, candidate)| candidate)
 }
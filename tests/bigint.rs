@@ -0,0 +1,150 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::object::BigInt;
+
+    fn big(s: &str) -> BigInt {
+        BigInt::parse(s).expect("faild: BigInt::parse")
+    }
+
+    #[test]
+    fn is_should_parse_and_display() {
+        let test_cases = vec![
+            ("0", "0"),
+            ("-0", "0"),
+            ("000123", "123"),
+            ("1000000000", "1000000000"),
+            ("-999999999999999999999", "-999999999999999999999"),
+            (
+                "123456789012345678901234567890",
+                "123456789012345678901234567890",
+            ),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(big(input).to_string(), expected);
+        }
+
+        assert_eq!(BigInt::parse(""), None);
+        assert_eq!(BigInt::parse("-"), None);
+        assert_eq!(BigInt::parse("12a"), None);
+    }
+
+    #[test]
+    fn is_should_convert_from_and_to_i64() {
+        let test_cases = vec![0, 1, -1, 999_999_999, 1_000_000_000, i64::MAX, i64::MIN];
+
+        for x in test_cases {
+            assert_eq!(BigInt::from_i64(x).to_string(), x.to_string());
+            assert_eq!(BigInt::from_i64(x).to_i64(), Some(x));
+        }
+
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert_eq!(big("-9223372036854775809").to_i64(), None);
+    }
+
+    #[test]
+    fn is_should_do_arithmetic() {
+        let test_cases = vec![
+            ("999999999", "+", "1", "1000000000"),
+            ("-5", "+", "3", "-2"),
+            ("5", "+", "-5", "0"),
+            ("1000000000000000000", "-", "1", "999999999999999999"),
+            ("1", "-", "1000000000000000000", "-999999999999999999"),
+            (
+                "-123456789123",
+                "*",
+                "987654321987",
+                "-121932631355968601347401",
+            ),
+            ("0", "*", "-5", "0"),
+        ];
+
+        for (left, operator, right, expected) in test_cases {
+            let (left, right) = (big(left), big(right));
+            let result = match operator {
+                "+" => &left + &right,
+                "-" => &left - &right,
+                "*" => &left * &right,
+                _ => unreachable!(),
+            };
+
+            assert_eq!(result.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn is_should_divide_toward_zero() {
+        let test_cases = vec![
+            ("7", "2", "3", "1"),
+            ("-7", "2", "-3", "-1"),
+            ("7", "-2", "-3", "1"),
+            ("-7", "-2", "3", "-1"),
+            ("1", "1000000000000", "0", "1"),
+            (
+                "-121932631355968601347401",
+                "987654321987",
+                "-123456789123",
+                "0",
+            ),
+            (
+                "100000000000000000000000000001",
+                "1000000007",
+                "99999999300000004899",
+                "999965708",
+            ),
+        ];
+
+        for (left, right, quotient, remainder) in test_cases {
+            let (q, r) = big(left).div_rem(&big(right)).unwrap();
+
+            assert_eq!(q.to_string(), quotient, "{} / {}", left, right);
+            assert_eq!(r.to_string(), remainder, "{} % {}", left, right);
+        }
+
+        assert_eq!(big("1").div_rem(&big("0")), None);
+    }
+
+    #[test]
+    fn is_should_raise_to_a_power_within_a_limit() {
+        let googol = format!("1{}", "0".repeat(100));
+        let test_cases = vec![
+            ("2", 0, 100, Some("1")),
+            ("2", 64, 100, Some("18446744073709551616")),
+            ("-3", 3, 100, Some("-27")),
+            ("10", 100, 101, Some(googol.as_str())),
+            ("10", 101, 101, None),
+            ("2", u64::MAX, 100, None),
+            ("-1", u64::MAX, 1, Some("-1")),
+        ];
+
+        for (base, exponent, max_digits, expected) in test_cases {
+            let result = big(base).checked_pow(exponent, max_digits);
+
+            assert_eq!(result.map(|x| x.to_string()).as_deref(), expected);
+        }
+    }
+
+    #[test]
+    fn is_should_order_by_value() {
+        let ordered = [
+            "-100000000000000000000",
+            "-99999999999999999999",
+            "-1",
+            "0",
+            "1",
+            "999999999",
+            "1000000000",
+            "100000000000000000000",
+        ];
+
+        for pair in ordered.windows(2) {
+            assert!(big(pair[0]) < big(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!(big("10").digits(), 2);
+        assert_eq!(big("-1000000000").digits(), 10);
+        assert_eq!(big("0").digits(), 1);
+    }
+}
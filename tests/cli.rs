@@ -0,0 +1,27 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use std::process::Command;
+
+    #[test]
+    fn is_should_print_usage_for_a_command_without_files() {
+        let test_cases = vec!["check", "lint", "fmt", "ast", "callgraph"];
+
+        for command in test_cases {
+            let output = Command::new(env!("CARGO_BIN_EXE_lolo"))
+                .arg(command)
+                .output()
+                .unwrap();
+
+            let stderr = String::from_utf8(output.stderr).unwrap();
+            assert!(!output.status.success(), "lolo {}", command);
+            assert!(
+                stderr.starts_with("usage: lolo"),
+                "lolo {}: {}",
+                command,
+                stderr
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::parser::cst::typed::{Expr, Root, Statement};
+    use lolo::parser::cst::{SyntaxElement, SyntaxKind, SyntaxNode};
+    use lolo::parser::{parse, parse_lossless};
+
+    #[test]
+    fn is_should_reproduce_source_exactly() {
+        let test_cases = vec![
+            "",
+            "   \n\t ",
+            "// only a comment",
+            "let x = 5;",
+            "let   add=fn(x,y){x+y};   // sum\n\n\nadd(1,\n  2)\n",
+            "if (a < b) {\r\n\treturn a;\r\n} else { b } // done\r\n",
+            "let f = fn() { // nothing\n};\nf()(1)(2, 3)",
+            "-(1 + 2) * !true / (3)",
+            "let = 5; 1 + ; add(1, 2",
+            "a @ b # c",
+            "let ñame = 1; // ünïcode ✓",
+            "a\0b",
+            "x // nul \0 in a comment\ny",
+        ];
+
+        for input in test_cases {
+            let (tree, _) = parse_lossless(input);
+
+            assert_eq!(tree.to_string(), input);
+        }
+    }
+
+    #[test]
+    fn is_should_build_nodes() {
+        let (tree, errors) = parse_lossless("let x = 1 + f(2); // c\nx");
+        assert!(errors.is_empty());
+
+        assert_eq!(
+            outline(&tree),
+            "Root(LetStatement(InfixExpression(Literal CallExpression(Name ArgumentList(Literal)))) ExpressionStatement(Name))"
+        );
+
+        let kinds: Vec<&str> = tree
+            .children
+            .iter()
+            .map(|x| match x {
+                SyntaxElement::Node(_) => "node",
+                SyntaxElement::Token(_) => "trivia",
+            })
+            .collect();
+        assert_eq!(kinds, vec!["node", "trivia", "trivia", "trivia", "node"]);
+    }
+
+    #[test]
+    fn is_should_give_a_typed_view() {
+        let source = "let add = fn(x, y) { x + y };\nif ((a)) { add(1, 2) } else { return -b; }";
+        let (tree, _) = parse_lossless(source);
+        let root = Root::cast(&tree).unwrap();
+        let statements = root.statements();
+
+        assert_eq!(statements.len(), 2);
+
+        let let_statement = match statements[0] {
+            Statement::Let(x) => x,
+            _ => panic!(),
+        };
+        assert_eq!(let_statement.name().unwrap().literal, "add");
+
+        let function = match let_statement.value().unwrap() {
+            Expr::Function(x) => x,
+            _ => panic!(),
+        };
+        let parameters: Vec<String> = function
+            .parameters()
+            .iter()
+            .map(|x| x.token().unwrap().literal.clone())
+            .collect();
+        assert_eq!(parameters, vec!["x", "y"]);
+        assert_eq!(function.body().unwrap().syntax.to_string(), "{ x + y }");
+
+        let if_expression = match statements[1] {
+            Statement::Expression(x) => match x.expression().unwrap() {
+                Expr::If(x) => x,
+                _ => panic!(),
+            },
+            _ => panic!(),
+        };
+
+        match if_expression.condition().unwrap() {
+            Expr::Paren(x) => assert_eq!(x.inner().unwrap().syntax().to_string(), "a"),
+            _ => panic!(),
+        }
+
+        let call = match if_expression.consequence().unwrap().statements()[0] {
+            Statement::Expression(x) => match x.expression().unwrap() {
+                Expr::Call(x) => x,
+                _ => panic!(),
+            },
+            _ => panic!(),
+        };
+        assert_eq!(call.function().unwrap().syntax().to_string(), "add");
+        assert_eq!(call.arguments().len(), 2);
+
+        match if_expression.alternative().unwrap().statements()[0] {
+            Statement::Return(x) => match x.value().unwrap() {
+                Expr::Prefix(x) => {
+                    assert_eq!(x.operator().unwrap().literal, "-");
+                    assert_eq!(x.operand().unwrap().syntax().to_string(), "b");
+                }
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn is_should_give_a_typed_view_of_assignments() {
+        let (tree, _) = parse_lossless("x  +=  // more\n 1;");
+        let statements = Root::cast(&tree).unwrap().statements();
+
+        let assignment = match statements[0] {
+            Statement::Assign(x) => x,
+            _ => panic!(),
+        };
+        assert_eq!(assignment.name().unwrap().literal, "x");
+        assert_eq!(assignment.operator().unwrap().literal, "+=");
+        assert_eq!(assignment.value().unwrap().syntax().to_string(), "1");
+    }
+
+    #[test]
+    fn is_should_give_a_typed_view_of_constants() {
+        let (tree, _) = parse_lossless("const x = 1; let y = 2;");
+        let statements = Root::cast(&tree).unwrap().statements();
+
+        let constness: Vec<bool> = statements
+            .iter()
+            .map(|x| match x {
+                Statement::Let(x) => x.is_const(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(constness, vec![true, false]);
+    }
+
+    #[test]
+    fn is_should_agree_with_the_ast() {
+        let test_cases = vec![
+            "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10);",
+            "// leading\nlet x = 1; x; -x; (x)",
+            "let x = 1; x = x + 1; x *= 2;",
+        ];
+
+        for input in test_cases {
+            let program = parse(input).unwrap();
+            let (tree, errors) = parse_lossless(input);
+
+            assert!(errors.is_empty());
+            assert_eq!(
+                Root::cast(&tree).unwrap().statements().len(),
+                program.statements.len()
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_report_the_same_errors() {
+        let input = "let = 5; add(1, 2";
+        let (_, errors) = parse_lossless(input);
+
+        assert_eq!(errors, parse(input).unwrap_err());
+    }
+
+    #[test]
+    fn is_should_span_nodes() {
+        let (tree, _) = parse_lossless("  // c\n  let x = 1;  ");
+        let span = tree.nodes().next().unwrap().span().unwrap();
+
+        assert_eq!((span.line, span.column), (2, 3));
+        assert_eq!((span.start, span.end), (9, 19));
+        assert_eq!(tree.nodes().next().unwrap().kind, SyntaxKind::LetStatement);
+    }
+
+    fn outline(node: &SyntaxNode) -> String {
+        let children: Vec<String> = node.nodes().map(outline).collect();
+
+        if children.is_empty() {
+            format!("{:?}", node.kind)
+        } else {
+            format!("{:?}({})", node.kind, children.join(" "))
+        }
+    }
+}
@@ -0,0 +1,101 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::diagnostics::Diagnostic;
+    use lolo::lexer::token::Span;
+    use lolo::lexer::Lexer;
+    use lolo::object::Environment;
+    use lolo::parser::Parser;
+
+    #[test]
+    fn is_should_render_source_snippets() {
+        let source = "let f = fn(x) { x };\nf(1, 2)";
+
+        let lexer = Lexer::new(&source);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+
+        let expected = "error[E0102]: wrong number of arguments: expected 1, got 2
+ --> main.lolo:2:1
+  |
+2 | f(1, 2)
+  | ^^^^^^^ expected 1 argument
+  |
+1 | let f = fn(x) { x };
+  |         -- function defined here
+";
+
+        assert_eq!(
+            error.diagnostic.render(source, "main.lolo", false),
+            expected
+        );
+    }
+
+    #[test]
+    fn is_should_render_notes_and_colors() {
+        let source = "1 + ;";
+
+        let lexer = Lexer::new(&source);
+        let mut parser = Parser::new(lexer);
+        parser.parse_program();
+
+        let diagnostic = parser.errors()[0]
+            .clone()
+            .with_note("an operator needs an expression on both sides");
+
+        let expected = "error[E0002]: no prefix parse function for SEMICOLON found
+ --> main.lolo:1:5
+  |
+1 | 1 + ;
+  |     ^ expected an expression
+  = note: an operator needs an expression on both sides
+";
+        assert_eq!(diagnostic.render(source, "main.lolo", false), expected);
+
+        let colored = diagnostic.render(source, "main.lolo", true);
+        assert!(colored.starts_with("\x1b[1;31merror[E0002]\x1b[0m"));
+        assert!(colored.contains("\x1b[1;31m^\x1b[0m"));
+    }
+
+    #[test]
+    fn is_should_render_without_span() {
+        let diagnostic = Diagnostic::warning("W0001", "something odd");
+
+        assert_eq!(
+            diagnostic.render("", "main.lolo", false),
+            "warning[W0001]: something odd\n"
+        );
+
+        let diagnostic = Diagnostic::warning("W0001", "something odd")
+            .with_primary(Span::default(), "somewhere")
+            .with_secondary(Span::default(), "elsewhere")
+            .with_help("look closer");
+
+        assert_eq!(
+            diagnostic.render("let x = 1;", "main.lolo", false),
+            "warning[W0001]: something odd\n = help: look closer\n"
+        );
+    }
+
+    #[test]
+    fn is_should_render_traceback() {
+        let source = "let f = fn() { g };\nf()";
+
+        let lexer = Lexer::new(&source);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+
+        let expected = "error[E0100]: identifier not found: g
+ --> main.lolo:1:16
+  |
+1 | let f = fn() { g };
+  |                ^ not found in this scope
+  at f (main.lolo:2:1)
+";
+
+        assert_eq!(error.render(source, "main.lolo", false), expected);
+    }
+}
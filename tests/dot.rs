@@ -0,0 +1,97 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::dot::{ast, call_graph};
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_render_the_ast() {
+        let input = "let a = -f(1) * 2;\nif (a) {} else { fn(x, y) { x } }";
+        let expected = r#"digraph ast {
+    node [shape=box, fontname=monospace];
+    n0 [label="program"];
+    n1 [label="let a"];
+    n2 [label="*"];
+    n3 [label="-"];
+    n4 [label="call"];
+    n5 [label="f"];
+    n4 -> n5 [label="function"];
+    n6 [label="1"];
+    n4 -> n6 [label="0"];
+    n3 -> n4;
+    n2 -> n3;
+    n7 [label="2"];
+    n2 -> n7;
+    n1 -> n2;
+    n0 -> n1;
+    n8 [label="if"];
+    n9 [label="a"];
+    n8 -> n9 [label="condition"];
+    n10 [label="block"];
+    n8 -> n10 [label="then"];
+    n11 [label="block"];
+    n8 -> n11 [label="else"];
+    n12 [label="fn(x, y)"];
+    n13 [label="block"];
+    n12 -> n13 [label="body"];
+    n14 [label="x"];
+    n13 -> n14;
+    n11 -> n12;
+    n0 -> n8;
+}"#;
+
+        assert_eq!(ast(&parse(input).unwrap()), expected);
+    }
+
+    #[test]
+    fn is_should_render_the_call_graph() {
+        let test_cases = vec![
+            ("f()", vec![]),
+            (
+                "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10)",
+                vec!["n0 -> n0"],
+            ),
+            // bodies see the functions bound after them
+            (
+                "let even = fn(n) { odd(n) }; let odd = fn(n) { even(n) };",
+                vec!["n0 -> n1", "n1 -> n0"],
+            ),
+            // through another let, and from inside an anonymous function
+            (
+                "let f = fn() { 1 }; let g = f; let h = fn() { fn() { g() } };",
+                vec!["n1 -> n0"],
+            ),
+            // parameters and later lets hide the function
+            (
+                "let f = fn() { 1 }; let g = fn(f) { f() }; let h = fn() { let f = 1; f() };",
+                vec![],
+            ),
+            (
+                "let main = fn() { let inner = fn() { main() }; inner() };",
+                vec!["n0 -> n1", "n1 -> n0"],
+            ),
+        ];
+
+        for (input, edges) in test_cases {
+            let graph = call_graph(&parse(input).unwrap());
+            let actual: Vec<&str> = graph
+                .lines()
+                .filter(|x| x.contains("->"))
+                .map(|x| x.trim().trim_end_matches(';'))
+                .collect();
+
+            assert_eq!(actual, edges, "{}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_name_the_functions() {
+        let graph = call_graph(&parse("let f = fn() { g() }; let g = fn() { 1 };").unwrap());
+
+        assert_eq!(
+            graph,
+            "digraph calls {\n    n0 [label=\"f\"];\n    n1 [label=\"g\"];\n    n0 -> n1;\n}"
+        );
+    }
+}
@@ -2,9 +2,13 @@ extern crate lolo;
 
 #[cfg(test)]
 mod test {
+    use lolo::evaluator::{MAX_CALL_DEPTH, MAX_EVAL_DEPTH, MAX_TRACE_FRAMES, STACK_SIZE};
+    use lolo::lexer::token::{Token, TokenType};
     use lolo::lexer::*;
     use lolo::object::*;
+    use lolo::parser::ast::expressions::*;
     use lolo::parser::*;
+    use std::thread;
 
     #[test]
     fn is_should_eval_integer_expression() {
@@ -25,6 +29,40 @@ mod test {
         }
     }
 
+    #[test]
+    fn is_should_eval_modulo_exponent_and_bitwise_operators() {
+        let test_cases = vec![
+            ("7 % 3", 1),
+            ("-7 % 3", -1),
+            ("2 ** 10", 1024),
+            ("2 ** 3 ** 2", 512),
+            ("-2 ** 2", -4),
+            ("(-2) ** 3", -8),
+            ("5 ** 0", 1),
+            ("6 & 3", 2),
+            ("6 | 3", 7),
+            ("6 ^ 3", 5),
+            ("~0", -1),
+            ("~5", -6),
+            ("1 << 4", 16),
+            ("-16 >> 2", -4),
+            ("1 << 64", 0),
+            ("1 >> 64", 0),
+            ("-1 >> 64", -1),
+            ("1 | 2 << 1 & 7", 5),
+            ("2 ** 62", 4611686018427387904),
+            ("1 ** 9999999999", 1),
+            ("(-1) ** 9999999999", -1),
+            ("0 ** 9999999999", 0),
+            ("-9223372036854775807 - 1 + 1", -9223372036854775807),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_integer_object(&evaluated, t.1);
+        }
+    }
+
     #[test]
     fn is_should_eval_boolean_expression() {
         let test_cases = vec![
@@ -40,6 +78,48 @@ mod test {
             ("1 == 2", false),
             ("1 != 1", false),
             ("1 != 2", true),
+            ("1 <= 2", true),
+            ("2 <= 2", true),
+            ("3 <= 2", false),
+            ("1 >= 2", false),
+            ("2 >= 2", true),
+            ("true && true", true),
+            ("true && false", false),
+            ("false || true", true),
+            ("false || false", false),
+            ("1 && 0", true),
+            ("1 < 2 && 2 < 3 || false", true),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_boolean_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_compare_values_of_any_type_for_equality() {
+        let test_cases = vec![
+            ("1 == true", false),
+            ("1 != true", true),
+            ("true == 1", false),
+            ("let n = if (false) { 1 }; n == n", true),
+            ("let n = if (false) { 1 }; n != n", false),
+            ("let n = if (false) { 1 }; n == false", false),
+            ("let n = if (false) { 1 }; 0 == n", false),
+            ("let f = fn(x) { x }; f == f", true),
+            ("let f = fn(x) { x }; f == 1", false),
+            (
+                "let f = fn(x) { x }; let g = fn(x) { x + 1 }; f == g",
+                false,
+            ),
+            ("let make = fn(n) { fn() { n } }; make(1) == make(1)", false),
+            ("let f = fn(x) { x }; let g = fn(x) {\n  x\n}; f == g", true),
+            (
+                "let f = fn(x) { f(x) }; let g = fn(x) { f(x) }; f == g",
+                true,
+            ),
+            ("2 ** 64 == true", false),
         ];
 
         for t in test_cases {
@@ -80,12 +160,506 @@ mod test {
         }
     }
 
+    #[test]
+    fn is_should_eval_let_statements() {
+        let test_cases = vec![
+            ("let a = 5; a;", 5),
+            ("let a = 5 * 5; a;", 25),
+            ("let a = 5; let b = a; b;", 5),
+            ("let a = 5; let b = a; let c = a + b + 5; c;", 15),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_integer_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_eval_return_statements() {
+        let test_cases = vec![
+            ("return 10;", 10),
+            ("return 10; 9;", 10),
+            ("return 2 * 5; 9;", 10),
+            ("9; return 2 * 5; 9;", 10),
+            ("if (10 > 1) { if (10 > 1) { return 10; } return 1; }", 10),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_integer_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_eval_function_application() {
+        let test_cases = vec![
+            ("let identity = fn(x) { x; }; identity(5);", 5),
+            ("let identity = fn(x) { return x; }; identity(5);", 5),
+            ("let double = fn(x) { x * 2; }; double(5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5, 5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5 + 5, add(5, 5));", 20),
+            ("fn(x) { x; }(5)", 5),
+            (
+                "let adder = fn(x) { fn(y) { x + y } }; let addTwo = adder(2); addTwo(3);",
+                5,
+            ),
+            (
+                "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10);",
+                55,
+            ),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_integer_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_promote_integers_that_overflow() {
+        let test_cases = vec![
+            ("9223372036854775807 + 1", "9223372036854775808"),
+            ("-9223372036854775807 - 2", "-9223372036854775809"),
+            ("3037000500 * 3037000500", "9223372037000250000"),
+            (
+                "let min = -9223372036854775807 - 1; min / -1",
+                "9223372036854775808",
+            ),
+            ("let min = -9223372036854775807 - 1; min % -1", "0"),
+            (
+                "let min = -9223372036854775807 - 1; -min",
+                "9223372036854775808",
+            ),
+            ("let x = 2; x ** 63", "9223372036854775808"),
+            ("2 ** 100", "1267650600228229401496703205376"),
+            (
+                "99999999999999999999 * 99999999999999999999",
+                "9999999999999999999800000000000000000001",
+            ),
+            ("-(2 ** 64) / 3", "-6148914691236517205"),
+            ("-(2 ** 64) % 3", "-1"),
+            ("10 ** 30 / 10 ** 28", "100"),
+            ("2 ** 64 - 2 ** 64 + 1", "1"),
+            ("(-3) ** 41", "-36472996377170786403"),
+            ("1 ** (2 ** 64)", "1"),
+            ("(-1) ** (2 ** 64 + 1)", "-1"),
+        ];
+
+        for t in test_cases {
+            assert_eq!(test_eval(t.0).inspect(), t.1, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_store_integers_that_fit_in_64_bits_as_integer() {
+        let test_cases = vec![
+            ("2 ** 64 - 2 ** 64", 0),
+            ("(2 ** 64) / (2 ** 60)", 16),
+            ("-9223372036854775808", i64::MIN),
+            ("00000000000000000000000000001", 1),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_integer_object(&evaluated, t.1);
+        }
+
+        assert_eq!(test_eval("2 ** 64").type_name(), "BIGINT");
+    }
+
+    #[test]
+    fn is_should_compare_integers_across_representations() {
+        let test_cases = vec![
+            ("2 ** 64 > 1", true),
+            ("1 < 2 ** 64", true),
+            ("-(2 ** 64) < -1", true),
+            ("2 ** 64 == 18446744073709551616", true),
+            ("2 ** 64 != 2 ** 64 + 1", true),
+            ("2 ** 64 - 1 >= 18446744073709551615", true),
+            ("2 ** 64 <= 2 ** 63", false),
+            ("(2 ** 64) / 2 == 2 ** 63", true),
+            ("2 ** 64 - 2 ** 64 == 0", true),
+            ("2 ** 64 == 0", false),
+            ("!(2 ** 64)", false),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_boolean_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_eval_null_and_nullish_operator() {
+        let test_cases = vec![
+            ("null", "null"),
+            ("null ?? 1", "1"),
+            ("2 ?? 1", "2"),
+            ("false ?? 1", "false"),
+            ("0 ?? undefined", "0"),
+            ("null ?? null ?? 3", "3"),
+            ("let f = fn() { if (false) { 1 } }; f() ?? 5", "5"),
+            ("null == null", "true"),
+            ("null != false", "true"),
+            ("!null", "true"),
+            ("if (null) { 1 } else { 2 }", "2"),
+            ("let x = null; x ?? 1 + 1", "2"),
+        ];
+
+        for t in test_cases {
+            assert_eq!(test_eval(t.0).inspect(), t.1, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_eval_assignments() {
+        let test_cases = vec![
+            ("let x = 1; x = 2; x", "2"),
+            ("let x = 1; x = x + 1; x = x * 10; x", "20"),
+            ("let x = 5; x += 3; x", "8"),
+            ("let x = 5; x -= 3; x", "2"),
+            ("let x = 5; x *= 3; x", "15"),
+            ("let x = 7; x /= 2; x", "3"),
+            ("let x = 1; x = true; x", "true"),
+            ("let x = 1; x = 2", "null"),
+            ("let x = 1; if (true) { x = 2; } x", "2"),
+            (
+                "let counter = fn() { let n = 0; fn() { n += 1; n } }; let c = counter(); c(); c(); c()",
+                "3",
+            ),
+            (
+                "let x = 1; let f = fn() { let x = 10; x = 20; x }; f() + x",
+                "21",
+            ),
+            ("let f = fn(n) { n = n * 2; n }; let n = 3; f(n) + n", "9"),
+            ("let x = 9223372036854775807; x += 1; x", "9223372036854775808"),
+            // the left operand is read before the right one runs
+            ("let x = 1; let f = fn() { x = 10; 1 }; x + f()", "2"),
+            ("let x = 1; let f = fn() { x = 10; 1 }; f() + x", "11"),
+            ("let x = 1; let f = fn() { x = 10; 1 }; x += f(); x", "2"),
+        ];
+
+        for t in test_cases {
+            assert_eq!(test_eval(t.0).inspect(), t.1, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_eval_constants() {
+        let test_cases = vec![
+            ("const x = 5; x", "5"),
+            ("const double = fn(n) { n * 2 }; double(3)", "6"),
+            (
+                "const x = 1; let f = fn() { let x = 2; x = 3; x }; f() + x",
+                "4",
+            ),
+            ("const x = 1; let f = fn(x) { x += 1; x }; f(5)", "6"),
+            ("let x = 1; const x = 2; x", "2"),
+        ];
+
+        for t in test_cases {
+            assert_eq!(test_eval(t.0).inspect(), t.1, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_point_at_the_declaration_of_a_changed_constant() {
+        let test_cases = vec![
+            ("const x = 1; x = 2", "assignment to constant: x", 14, 7),
+            ("const x = 1; x += 1", "assignment to constant: x", 14, 7),
+            (
+                "const x = 1; let f = fn() { x = 2 }; f()",
+                "assignment to constant: x",
+                29,
+                7,
+            ),
+            (
+                "const x = 1; let x = 2;",
+                "redeclaration of constant: x",
+                18,
+                7,
+            ),
+            (
+                "const x = 1; if (true) { const x = 2; }",
+                "redeclaration of constant: x",
+                32,
+                7,
+            ),
+        ];
+
+        for t in test_cases {
+            let lexer = Lexer::new(&t.0);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+
+            let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+            let diagnostic = error.diagnostic;
+            assert_eq!(diagnostic.message, t.1, "input: {}", t.0);
+            assert_eq!(diagnostic.primary.unwrap().span.column, t.2);
+            assert_eq!(diagnostic.secondary[0].span.column, t.3);
+            assert_eq!(diagnostic.secondary[0].message, "declared as constant here");
+        }
+    }
+
+    #[test]
+    fn is_should_short_circuit_logical_operators() {
+        let test_cases = vec![
+            ("let x = 0; x != 0 && 10 / x > 1", false),
+            ("let x = 5; x != 0 && 10 / x > 1", true),
+            ("false && undefined", false),
+            ("true || undefined()", true),
+            ("let f = fn() { return false; 1 }; true && f()", false),
+        ];
+
+        for t in test_cases {
+            let evaluated = test_eval(t.0);
+            test_boolean_object(&evaluated, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_return_error_for_invalid_identifier_and_call() {
+        let test_cases = vec![
+            ("foobar", "identifier not found: foobar"),
+            ("let x = 1; x(1)", "not a function: 1"),
+            (
+                "let f = fn(x) { x }; f(1, 2)",
+                "wrong number of arguments: expected 1, got 2",
+            ),
+            ("1 << -1", "negative shift amount: -1"),
+            ("let n = 3; 8 >> 1 - n", "negative shift amount: -2"),
+            ("2 ** -1", "negative exponent: -1"),
+            ("~true", "unknown operator: ~BOOLEAN"),
+            ("1 / 0", "attempt to divide by zero"),
+            ("let x = 0; 5 % x", "attempt to divide by zero"),
+            (
+                "2 ** 40000",
+                "integer overflow: the result of ** has more than 10000 digits",
+            ),
+            (
+                "let x = 10 ** 5000; x * x * 10",
+                "integer overflow: the result of * has more than 10000 digits",
+            ),
+            ("2 ** 64 / 0", "attempt to divide by zero"),
+            ("2 ** 64 % (1 - 1)", "attempt to divide by zero"),
+            (
+                "2 ** (-2 ** 64)",
+                "negative exponent: -18446744073709551616",
+            ),
+            ("2 ** 64 & 1", "unknown operator: BIGINT & INTEGER"),
+            ("1 << 2 ** 64", "unknown operator: INTEGER << BIGINT"),
+            ("~(2 ** 64)", "unknown operator: ~BIGINT"),
+            ("x = 1", "assignment to undeclared variable: x"),
+            ("x += 1", "assignment to undeclared variable: x"),
+            (
+                "let f = fn() { y = 1 }; f()",
+                "assignment to undeclared variable: y",
+            ),
+            ("let x = true; x += 1", "type mismatch: BOOLEAN + INTEGER"),
+            ("let x = 1; x /= 0", "attempt to divide by zero"),
+            ("1 < true", "type mismatch: INTEGER < BOOLEAN"),
+            ("false >= 0", "type mismatch: BOOLEAN >= INTEGER"),
+            ("2 ** 64 > false", "type mismatch: BIGINT > BOOLEAN"),
+            ("true + 1", "type mismatch: BOOLEAN + INTEGER"),
+            ("true < false", "unknown operator: BOOLEAN < BOOLEAN"),
+            ("true + true", "unknown operator: BOOLEAN + BOOLEAN"),
+            (
+                "let f = fn() { 1 }; f > f",
+                "unknown operator: FUNCTION > FUNCTION",
+            ),
+            (
+                "let n = if (false) { 1 }; n <= 1",
+                "type mismatch: NULL <= INTEGER",
+            ),
+        ];
+
+        for t in test_cases {
+            let lexer = Lexer::new(&t.0);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+
+            let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+            assert_eq!(error.to_string(), t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_trace_function_calls_on_error() {
+        let input = "let inner = fn(n) {
+  if (n < 1) { return missing; }
+  inner(n - 1)
+};
+let outer = fn() { inner(1) };
+fn() { outer() }()";
+
+        let lexer = Lexer::new(&input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+        let trace: Vec<(Option<&str>, usize, usize)> = error
+            .trace
+            .iter()
+            .map(|f| (f.name.as_deref(), f.call_site.line, f.call_site.column))
+            .collect();
+
+        assert_eq!(
+            trace,
+            vec![
+                (Some("inner"), 3, 3),
+                (Some("inner"), 5, 20),
+                (Some("outer"), 6, 8),
+                (None, 6, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_should_stop_runaway_recursion() {
+        on_interpreter_stack(|| {
+            let test_cases = vec![
+                "let f = fn(n) { f(n + 1) }; f(0)",
+                "let f = fn(n) { if (n == 0) { return 0; } return f(n - 1); }; f(100000)",
+                "let f = fn(n) { if (n == 0) { 0 } else { let r = 1 + f(n - 1) * 1; r } }; f(100000)",
+            ];
+
+            for input in test_cases {
+                assert_eq!(
+                    test_eval_error(input).to_string(),
+                    "maximum call depth of 1000 exceeded",
+                    "input: {}",
+                    input
+                );
+            }
+
+            // the deepest call allowed, which also shows the calls left by the
+            // errors above do not count against it
+            let input = format!(
+                "let f = fn(n) {{ if (n == 0) {{ 0 }} else {{ 1 + f(n - 1) }} }}; f({})",
+                MAX_CALL_DEPTH - 1
+            );
+            assert_eq!(
+                test_eval(&input).inspect(),
+                (MAX_CALL_DEPTH - 1).to_string()
+            );
+        });
+    }
+
+    #[test]
+    fn is_should_limit_expression_depth() {
+        on_interpreter_stack(|| {
+            // deeper than the parser allows, as a tree built by hand may be
+            let one = Expression::IntegerLiteral(IntegerLiteral {
+                token: Token::new(TokenType::INT, &"1"),
+                value: 1,
+            });
+            let mut expression = one.clone();
+            for _ in 0..MAX_EVAL_DEPTH {
+                expression = Expression::InfixExpression(InfixExpression {
+                    token: Token::new(TokenType::PLUS, &"+"),
+                    operator: "+".to_string(),
+                    left: Box::new(expression),
+                    right: Box::new(one.clone()),
+                });
+            }
+
+            let error = lolo::evaluator::eval(&expression, &Environment::new()).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "maximum expression depth of 20000 exceeded"
+            );
+
+            // calls stack up expressions that each parse fine
+            let input = format!(
+                "let f = fn(n) {{ if (n == 0) {{ 0 }} else {{ {}f(n - 1){} }} }}; f(500)",
+                "(1 + ".repeat(100),
+                ")".repeat(100)
+            );
+            assert_eq!(
+                test_eval_error(&input).to_string(),
+                "maximum expression depth of 20000 exceeded"
+            );
+
+            // one below the limit
+            expression = match expression {
+                Expression::InfixExpression(x) => *x.left,
+                _ => unreachable!(),
+            };
+            assert_eq!(
+                lolo::evaluator::eval(&expression, &Environment::new())
+                    .unwrap()
+                    .inspect(),
+                MAX_EVAL_DEPTH.to_string()
+            );
+        });
+    }
+
+    #[test]
+    fn is_should_cut_long_tracebacks_down_to_the_innermost_calls() {
+        on_interpreter_stack(|| {
+            let error = test_eval_error("let f = fn(n) { f(n + 1) }; f(0)");
+
+            assert_eq!(error.trace.len(), MAX_TRACE_FRAMES);
+            assert!(error.trace.iter().all(|f| f.name.as_deref() == Some("f")));
+            assert_eq!(error.omitted_frames, MAX_CALL_DEPTH - MAX_TRACE_FRAMES);
+            assert!(error
+                .render("let f = fn(n) { f(n + 1) }; f(0)", "main.lo", false)
+                .ends_with("  at f (main.lo:1:17)\n  ... 990 more\n"));
+        });
+
+        let error =
+            test_eval_error("let f = fn(n) { if (n == 0) { missing } else { f(n - 1) } }; f(11)");
+        assert_eq!(error.trace.len(), MAX_TRACE_FRAMES);
+        assert_eq!(error.omitted_frames, 2);
+    }
+
+    #[test]
+    fn is_should_suggest_similar_names() {
+        let test_cases = vec![
+            ("let total = 1; totl", vec!["did you mean `total`?"]),
+            (
+                "let f = fn(count) { cont }; f(1)",
+                vec!["did you mean `count`?"],
+            ),
+            ("retrun", vec!["did you mean `return`?"]),
+            ("zzz", vec![]),
+        ];
+
+        for t in test_cases {
+            let lexer = Lexer::new(&t.0);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+
+            let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+            assert_eq!(error.diagnostic.helps, t.1);
+        }
+    }
+
+    // as much stack as the binary evaluates on, since test threads get far less
+    fn on_interpreter_stack(test: impl FnOnce() + Send + 'static) {
+        thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(test)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    fn test_eval_error(input: &str) -> lolo::evaluator::RuntimeError {
+        let lexer = Lexer::new(&input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        lolo::evaluator::eval(&program, &Environment::new()).unwrap_err()
+    }
+
     fn test_eval(input: &str) -> Object {
         let lexer = Lexer::new(&input);
         let mut parser = Parser::new(lexer);
         let program = parser.parse_program();
 
-        lolo::evaluator::eval(&program).expect("fald eval")
+        lolo::evaluator::eval(&program, &Environment::new()).expect("fald eval")
     }
 
     fn test_integer_object(obj: &Object, expected: i64) {
@@ -0,0 +1,101 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::formatter::format;
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_format_source() {
+        let test_cases = vec![
+            ("let   x=5", "let x = 5;\n"),
+            (
+                "let add=fn(x,y){x+y;};",
+                "let add = fn(x, y) {\n    x + y\n};\n",
+            ),
+            (
+                "if(a<b){\n\t\treturn a\n}else{b}",
+                "if (a < b) {\n    return a;\n} else {\n    b\n}\n",
+            ),
+            ("((1 + 2)) * (3)", "(1 + 2) * 3;\n"),
+            ("let x = 1;\n\n\n\nlet y = 2;", "let x = 1;\n\nlet y = 2;\n"),
+            ("f(\n  1,\n  2\n)", "f(1, 2);\n"),
+            ("", ""),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(format(input).unwrap(), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_keep_comments() {
+        let test_cases = vec![
+            ("// only a comment", "// only a comment\n"),
+            (
+                "// header\nlet x = 1; // one\n\n// before y\nlet y = 2;",
+                "// header\nlet x = 1; // one\n\n// before y\nlet y = 2;\n",
+            ),
+            (
+                "let f = fn(x) {\n  // the value\n  x // returned\n  // after\n};",
+                "let f = fn(x) {\n    // the value\n    x // returned\n    // after\n};\n",
+            ),
+            ("let f = fn() { // todo\n};", "let f = fn() { // todo\n};\n"),
+            (
+                "let f = fn() {\n// todo\n};",
+                "let f = fn() {\n    // todo\n};\n",
+            ),
+            ("x; // end\n// trailer", "x; // end\n// trailer\n"),
+            ("f(1, // first\n  2);", "f(\n    1, // first\n    2\n);\n"),
+            (
+                "f(\n  // the base\n  1,\n  g(2 // two\n  ));",
+                "f(\n    // the base\n    1,\n    g(\n        2 // two\n    )\n);\n",
+            ),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(format(input).unwrap(), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_wrap_long_calls() {
+        let input =
+            "let total = sum(firstArgument, secondArgument, thirdArgument, fourthArgument, 5);";
+        let expected = "let total = sum(
+    firstArgument,
+    secondArgument,
+    thirdArgument,
+    fourthArgument,
+    5
+);
+";
+
+        assert_eq!(format(input).unwrap(), expected);
+        assert_eq!(format(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn is_should_keep_meaning_and_be_stable() {
+        let test_cases = vec![
+            "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10);",
+            "let adder = fn(x) { fn(y) { x + y } };\n\n// curried\nadder(1)(2);",
+            "if (x) { 1 }; -1; (f)(2)",
+            "compose(fn(x) { x * 2 }, fn(x) { x + 1 })(veryLongArgumentName, anotherLongArgumentName)",
+        ];
+
+        for input in test_cases {
+            let formatted = format(input).unwrap();
+
+            assert_eq!(parse(&formatted).unwrap(), parse(input).unwrap());
+            assert_eq!(format(&formatted).unwrap(), formatted, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_report_parse_errors() {
+        let errors = format("let = 1;").unwrap_err();
+
+        assert_eq!(errors[0].code, "E0001");
+    }
+}
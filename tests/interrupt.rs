@@ -0,0 +1,25 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::interrupt;
+    use lolo::lexer::Lexer;
+    use lolo::object::Environment;
+    use lolo::parser::Parser;
+
+    // kept in its own test binary since the interrupt flag is process global
+    #[test]
+    fn is_should_abort_evaluation_when_interrupted() {
+        let input = "let loop = fn(n) { loop(n + 1) }; loop(0);";
+
+        let lexer = Lexer::new(&input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        interrupt::interrupt();
+        let error = lolo::evaluator::eval(&program, &Environment::new()).unwrap_err();
+        interrupt::clear();
+
+        assert!(error.is_interrupted());
+    }
+}
@@ -0,0 +1,98 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::json::{export, program, tokens, SCHEMA_VERSION};
+    use lolo::lexer::token::{Token, TokenType};
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_export_programs() {
+        let test_cases = vec![
+            (
+                "x",
+                r#"{"kind":"Program","statements":[{"kind":"ExpressionStatement","span":{"start":0,"end":1,"line":1,"column":1},"expression":{"kind":"Identifier","span":{"start":0,"end":1,"line":1,"column":1},"name":"x"}}]}"#,
+            ),
+            (
+                "let a = -1;",
+                r#"{"kind":"Program","statements":[{"kind":"LetStatement","span":{"start":0,"end":3,"line":1,"column":1},"name":{"kind":"Identifier","span":{"start":4,"end":5,"line":1,"column":5},"name":"a"},"value":{"kind":"PrefixExpression","span":{"start":8,"end":9,"line":1,"column":9},"operator":"-","right":{"kind":"IntegerLiteral","span":{"start":9,"end":10,"line":1,"column":10},"value":1}},"constant":false}]}"#,
+            ),
+            (
+                "const b = 2;",
+                r#"{"kind":"Program","statements":[{"kind":"LetStatement","span":{"start":0,"end":5,"line":1,"column":1},"name":{"kind":"Identifier","span":{"start":6,"end":7,"line":1,"column":7},"name":"b"},"value":{"kind":"IntegerLiteral","span":{"start":10,"end":11,"line":1,"column":11},"value":2},"constant":true}]}"#,
+            ),
+            (
+                "99999999999999999999",
+                r#"{"kind":"Program","statements":[{"kind":"ExpressionStatement","span":{"start":0,"end":20,"line":1,"column":1},"expression":{"kind":"BigIntLiteral","span":{"start":0,"end":20,"line":1,"column":1},"value":"99999999999999999999"}}]}"#,
+            ),
+            ("", r#"{"kind":"Program","statements":[]}"#),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(program(&parse(input).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn is_should_export_every_kind() {
+        let input =
+            "let f = fn(x) { return x * 2; };\nif (f(1) == true) { 1 } else { 2 ?? null };\nf = f";
+        let document = export(input).unwrap();
+
+        let kinds = vec![
+            "Program",
+            "LetStatement",
+            "AssignStatement",
+            "ReturnStatement",
+            "ExpressionStatement",
+            "Block",
+            "Identifier",
+            "IntegerLiteral",
+            "Boolean",
+            "NullLiteral",
+            "InfixExpression",
+            "IfExpression",
+            "FunctionLiteral",
+            "CallExpression",
+        ];
+        for kind in kinds {
+            assert!(
+                document.contains(&format!(r#""kind":"{}""#, kind)),
+                "{}",
+                kind
+            );
+        }
+
+        assert!(document.starts_with(&format!(
+            r#"{{"version":{},"tokens":[{{"type":"LET""#,
+            SCHEMA_VERSION
+        )));
+        assert!(document.contains(r#""alternative":{"kind":"Block""#));
+        assert!(export("if (a) { b }")
+            .unwrap()
+            .contains(r#""alternative":null"#));
+    }
+
+    #[test]
+    fn is_should_export_tokens() {
+        let test_cases = vec![
+            (
+                Token::new(TokenType::INT, &5),
+                r#"[{"type":"INT","literal":"5","span":{"start":0,"end":0,"line":0,"column":0}}]"#,
+            ),
+            (
+                Token::new(TokenType::ILLEGAL, &"\"\\\n\u{1}é"),
+                r#"[{"type":"ILLEGAL","literal":"\"\\\n\u0001é","span":{"start":0,"end":0,"line":0,"column":0}}]"#,
+            ),
+        ];
+
+        for (token, expected) in test_cases {
+            assert_eq!(tokens(&[token]), expected);
+        }
+    }
+
+    #[test]
+    fn is_should_not_export_broken_programs() {
+        assert!(export("let = 5;").is_err());
+    }
+}
@@ -3,6 +3,7 @@ extern crate lolo;
 #[cfg(test)]
 mod tests {
     use lolo::lexer::token::TokenType::*;
+    use lolo::lexer::token::KEYWORDS;
     use lolo::lexer::Lexer;
 
     #[test]
@@ -148,4 +149,260 @@ mod tests {
             assert_eq!(t.literal, literal.to_string());
         }
     }
+
+    #[test]
+    fn is_should_analysis_of_comparison_and_logical_operators() {
+        let input = "a <= 1 >= b && !c || d<=e; & |";
+
+        let expects = vec![
+            (IDENT, "a"),
+            (LTEQ, "<="),
+            (INT, "1"),
+            (GTEQ, ">="),
+            (IDENT, "b"),
+            (AND, "&&"),
+            (BANG, "!"),
+            (IDENT, "c"),
+            (OR, "||"),
+            (IDENT, "d"),
+            (LTEQ, "<="),
+            (IDENT, "e"),
+            (SEMICOLON, ";"),
+            (AMPERSAND, "&"),
+            (PIPE, "|"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_analysis_of_arithmetic_and_bitwise_operators() {
+        let input = "a%b ** c*d & e|f ^ ~g << 1 >> 2 ||h";
+
+        let expects = vec![
+            (IDENT, "a"),
+            (PERCENT, "%"),
+            (IDENT, "b"),
+            (POWER, "**"),
+            (IDENT, "c"),
+            (ASTERISK, "*"),
+            (IDENT, "d"),
+            (AMPERSAND, "&"),
+            (IDENT, "e"),
+            (PIPE, "|"),
+            (IDENT, "f"),
+            (CARET, "^"),
+            (TILDE, "~"),
+            (IDENT, "g"),
+            (SHIFTLEFT, "<<"),
+            (INT, "1"),
+            (SHIFTRIGHT, ">>"),
+            (INT, "2"),
+            (OR, "||"),
+            (IDENT, "h"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_analysis_of_null_and_nullish_operator() {
+        let input = "a ?? null??b ? c";
+
+        let expects = vec![
+            (IDENT, "a"),
+            (NULLISH, "??"),
+            (NULL, "null"),
+            (NULLISH, "??"),
+            (IDENT, "b"),
+            (ILLEGAL, "?"),
+            (IDENT, "c"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_analysis_of_const_keyword() {
+        let input = "const x = 1; constant";
+
+        let expects = vec![
+            (CONST, "const"),
+            (IDENT, "x"),
+            (ASSIGN, "="),
+            (INT, "1"),
+            (SEMICOLON, ";"),
+            (IDENT, "constant"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_analysis_of_every_keyword() {
+        for (keyword, token_type) in KEYWORDS.iter() {
+            let mut l = Lexer::new(keyword);
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, *token_type);
+            assert_eq!(t.literal, keyword.to_string());
+
+            // a longer word is only an identifier
+            let word = format!("{}s", keyword);
+            let mut l = Lexer::new(&word);
+            assert_eq!(l.next_token().token_type, IDENT);
+        }
+    }
+
+    #[test]
+    fn is_should_analysis_of_assignment_operators() {
+        let input = "x = 1; x += 2 -= 3*=4 /= y ** z";
+
+        let expects = vec![
+            (IDENT, "x"),
+            (ASSIGN, "="),
+            (INT, "1"),
+            (SEMICOLON, ";"),
+            (IDENT, "x"),
+            (PLUSASSIGN, "+="),
+            (INT, "2"),
+            (MINUSASSIGN, "-="),
+            (INT, "3"),
+            (ASTERISKASSIGN, "*="),
+            (INT, "4"),
+            (SLASHASSIGN, "/="),
+            (IDENT, "y"),
+            (POWER, "**"),
+            (IDENT, "z"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_record_token_spans() {
+        let input = "let x = 10;\n  x != y";
+
+        let expects = vec![
+            ("let", 0, 3, 1, 1),
+            ("x", 4, 5, 1, 5),
+            ("=", 6, 7, 1, 7),
+            ("10", 8, 10, 1, 9),
+            (";", 10, 11, 1, 11),
+            ("x", 14, 15, 2, 3),
+            ("!=", 16, 18, 2, 5),
+            ("y", 19, 20, 2, 8),
+            ("\0", 20, 20, 2, 9),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (literal, start, end, line, column) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.literal, literal.to_string());
+            assert_eq!(t.span.start, start);
+            assert_eq!(t.span.end, end);
+            assert_eq!(t.span.line, line);
+            assert_eq!(t.span.column, column);
+        }
+    }
+
+    #[test]
+    fn is_should_skip_comments() {
+        let input = "// leading comment
+        let x = 5; // trailing comment
+        x / 2 //";
+
+        let expects = vec![
+            (LET, "let"),
+            (IDENT, "x"),
+            (ASSIGN, "="),
+            (INT, "5"),
+            (SEMICOLON, ";"),
+            (IDENT, "x"),
+            (SLASH, "/"),
+            (INT, "2"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input);
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
+
+    #[test]
+    fn is_should_emit_trivia_when_asked() {
+        let input = "let x = 5; // five\n\tx";
+
+        let expects = vec![
+            (LET, "let"),
+            (WHITESPACE, " "),
+            (IDENT, "x"),
+            (WHITESPACE, " "),
+            (ASSIGN, "="),
+            (WHITESPACE, " "),
+            (INT, "5"),
+            (SEMICOLON, ";"),
+            (WHITESPACE, " "),
+            (COMMENT, "// five"),
+            (WHITESPACE, "\n\t"),
+            (IDENT, "x"),
+            (EOF, "\0"),
+        ];
+
+        let mut l = Lexer::new(&input).with_trivia();
+
+        for (token_type, literal) in expects {
+            let t = l.next_token();
+
+            assert_eq!(t.token_type, token_type);
+            assert_eq!(t.literal, literal.to_string());
+        }
+    }
 }
@@ -0,0 +1,201 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::diagnostics::Severity;
+    use lolo::lint::config::parse_directives;
+    use lolo::lint::{lint, Config, Level};
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_accept_clean_programs() {
+        let test_cases = vec![
+            "let x = 5; x + 1;",
+            "let add = fn(x, y) { x + y }; add(1, 2);",
+            "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10);",
+            "let f = fn() { g() }; let g = fn() { 1 }; f();",
+            "let x = 1; x == 1;",
+            "let f = fn() { 1 }; f() == f();",
+            "let x = 1; x += 1; x;",
+        ];
+
+        for input in test_cases {
+            assert!(
+                lint_input(input, &Config::default()).is_empty(),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_report_lints() {
+        let test_cases = vec![
+            (
+                "let x = 1;",
+                vec![("unused_variable", "unused variable: `x`", 1, 5)],
+            ),
+            (
+                "let f = fn(a, b) { a }; f(1, 2);",
+                vec![("unused_parameter", "unused parameter: `b`", 1, 15)],
+            ),
+            (
+                "let x = 1; let x = x + 1; x;",
+                vec![("shadowed_name", "`x` shadows an earlier binding", 1, 16)],
+            ),
+            (
+                "let f = fn(x) { return x; x + 1; }; f(1);",
+                vec![("unreachable_code", "unreachable statement", 1, 27)],
+            ),
+            (
+                "if (true) { 1 }",
+                vec![("constant_condition", "constant `if` condition", 1, 5)],
+            ),
+            (
+                "if (-1 < 2) { 1 }",
+                vec![("constant_condition", "constant `if` condition", 1, 5)],
+            ),
+            (
+                "let x = 1; x != x;",
+                vec![("self_comparison", "`x` is compared with itself", 1, 12)],
+            ),
+            (
+                "let x = 1;\nlet f = fn(y) { let x = 2; x };\nf(x);",
+                vec![
+                    ("unused_parameter", "unused parameter: `y`", 2, 12),
+                    ("shadowed_name", "`x` shadows an earlier binding", 2, 21),
+                ],
+            ),
+            (
+                "let x = 1; x = 2;",
+                vec![("unused_variable", "unused variable: `x`", 1, 5)],
+            ),
+            (
+                "let x = 1; let f = fn(x) { x }; f(x);",
+                vec![("shadowed_name", "`x` shadows an earlier binding", 1, 23)],
+            ),
+        ];
+
+        for t in test_cases {
+            let diagnostics: Vec<(String, String, usize, usize)> =
+                lint_input(t.0, &Config::default())
+                    .into_iter()
+                    .map(|d| {
+                        let span = d.primary.unwrap().span;
+                        (d.code, d.message, span.line, span.column)
+                    })
+                    .collect();
+
+            let expected: Vec<(String, String, usize, usize)> =
+                t.1.into_iter()
+                    .map(|(code, message, line, column)| {
+                        (code.to_string(), message.to_string(), line, column)
+                    })
+                    .collect();
+
+            assert_eq!(diagnostics, expected, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_follow_configured_levels() {
+        let config = Config::parse(
+            "# project lints
+[package]
+name = \"demo\"
+
+[lints]
+unused_variable = \"allow\"
+self_comparison = \"deny\" # never intended",
+        )
+        .unwrap();
+
+        assert_eq!(config.level("unused_variable"), Level::Allow);
+        assert_eq!(config.level("self_comparison"), Level::Deny);
+        assert_eq!(config.level("shadowed_name"), Level::Warn);
+
+        let diagnostics = lint_input("let x = 1; let y = 2; y == y;", &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "self_comparison");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn is_should_reject_invalid_config() {
+        let test_cases = vec![
+            (
+                "[lints]\nunused = \"allow\"",
+                "line 2: unknown lint `unused`",
+            ),
+            (
+                "[lints]\nunused_variable = \"off\"",
+                "line 2: unknown level \"off\", expected \"allow\", \"warn\" or \"deny\"",
+            ),
+            (
+                "[lints]\nunused_variable = allow",
+                "line 2: the level of `unused_variable` must be a quoted string",
+            ),
+            ("[lints\n", "line 1: unclosed table header"),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(Config::parse(input).unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn is_should_follow_inline_directives() {
+        let test_cases = vec![
+            ("let x = 1; // lolo: allow(unused_variable)", vec![]),
+            ("// lolo: allow(unused_variable)\n\nlet x = 1;", vec![]),
+            (
+                "// lolo: allow(unused_variable, shadowed_name)\nlet x = 1;\nlet y = 1;",
+                vec![("unused_variable", Severity::Warning)],
+            ),
+            (
+                "let x = 1; // lolo: deny(unused_variable)",
+                vec![("unused_variable", Severity::Error)],
+            ),
+            (
+                "let x = 1; x; // lolo: allow(unused)",
+                vec![("invalid_directive", Severity::Warning)],
+            ),
+            (
+                "let x = 1; x; // lolo: ignore everything",
+                vec![("invalid_directive", Severity::Warning)],
+            ),
+            ("let x = 1; x; // just a comment, lolo: allow(x)", vec![]),
+        ];
+
+        for (input, expected) in test_cases {
+            let diagnostics: Vec<(String, Severity)> = lint_input(input, &Config::default())
+                .into_iter()
+                .map(|d| (d.code, d.severity))
+                .collect();
+
+            let expected: Vec<(String, Severity)> = expected
+                .into_iter()
+                .map(|(code, severity)| (code.to_string(), severity))
+                .collect();
+
+            assert_eq!(diagnostics, expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_attach_directives_to_lines_of_code() {
+        let (directives, diagnostics) =
+            parse_directives("// lolo: allow(shadowed_name)\n// other\n\nlet x = 1;");
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(directives.len(), 1);
+        assert_eq!(directives[0].line, 4);
+        assert_eq!(directives[0].lints, vec!["shadowed_name"]);
+    }
+
+    fn lint_input(input: &str, config: &Config) -> Vec<lolo::diagnostics::Diagnostic> {
+        let program = parse(input).unwrap();
+
+        lint(&program, input, config)
+    }
+}
@@ -0,0 +1,149 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::evaluator::eval;
+    use lolo::object::Environment;
+    use lolo::optimizer::optimize;
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_fold_constants() {
+        let test_cases = vec![
+            ("1 + 2 * 3", "7"),
+            ("(10 - 4) / 3 * -2", "-4"),
+            ("--5", "5"),
+            ("-(2 - 7)", "5"),
+            ("!true", "false"),
+            ("!!5", "true"),
+            ("!-1", "false"),
+            ("1 < 2 == true", "true"),
+            ("3 > 4 != (1 == 1)", "true"),
+            ("true == false", "false"),
+            ("2 <= 1 || 3 >= 3", "true"),
+            ("2 ** 3 ** 2 % 100", "12"),
+            ("(6 & 3 | 8) ^ ~0 << 1 >> 1", "-11"),
+            ("1 << 70", "0"),
+            ("false && f()", "false"),
+            ("1 || f()", "true"),
+            ("true && 0", "true"),
+            ("true && f()", "true && f()"),
+            ("f() || true", "f() || true"),
+            ("null ?? f()", "f()"),
+            ("0 ?? f()", "0"),
+            ("-1 ?? f()", "-1"),
+            ("false ?? f()", "false"),
+            ("f() ?? 1", "f() ?? 1"),
+            ("!null", "true"),
+            ("null || 2", "true"),
+            ("x + 2 * 3", "x + 6"),
+            ("x / (1 - 1)", "x / 0"),
+            ("f(1 + 1, x * (2 - 2))", "f(2, x * 0)"),
+            ("let a = fn(x) { x * (1 + 1) };", "let a = fn(x) { x * 2 };"),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(
+                optimize(parse(input).unwrap()),
+                parse(expected).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_keep_what_fails_at_runtime() {
+        let test_cases = vec![
+            "1 / 0",
+            "9223372036854775807 + 1",
+            "-true",
+            "1 % 0",
+            "2 ** -1",
+            "1 >> -1",
+            "2 ** 64",
+            "true + false",
+            "1 == true",
+            "f(1)",
+        ];
+
+        for input in test_cases {
+            assert_eq!(
+                optimize(parse(input).unwrap()),
+                parse(input).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_prune_branches() {
+        let test_cases = vec![
+            ("if (true) { a } else { b }", "a"),
+            ("if (1 > 2) { a } else { b }", "b"),
+            ("if (0) { a }", "a"),
+            ("let x = if (!false) { 1 + 1 };", "let x = 2;"),
+            ("if (true) { let a = 1; a } b", "let a = 1; a; b"),
+            ("if (false) { a } b", "b"),
+            ("a; if (false) { b }", "a; if (false) {}"),
+            ("a; if (true) {} else { b }", "a; if (true) {}"),
+            (
+                "let x = if (true) { let a = 1; a };",
+                "let x = if (true) { let a = 1; a };",
+            ),
+            ("if (x) { 1 + 1 } else { 2 }", "if (x) { 2 } else { 2 }"),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(
+                optimize(parse(input).unwrap()),
+                parse(expected).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_drop_dead_code() {
+        let test_cases = vec![
+            ("fn() { return 1; 2; 3 }", "fn() { return 1; }"),
+            ("fn() { if (true) { return 1; } 2 }", "fn() { return 1; }"),
+            (
+                "fn() { if (x) { return 1; f() } 2 }",
+                "fn() { if (x) { return 1; } 2 }",
+            ),
+            ("return 1; let a = 2;", "return 1;"),
+        ];
+
+        for (input, expected) in test_cases {
+            assert_eq!(
+                optimize(parse(input).unwrap()),
+                parse(expected).unwrap(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn is_should_not_change_results() {
+        let test_cases = vec![
+            "let f = fn(x) { if (true) { return x * (2 + 3); } 0 }; f(2)",
+            "let f = fn() { 5; if (false) { 1 } }; f()",
+            "let f = fn() { if (1 < 2) { let y = 3; } y }; f()",
+            "let x = if (false) { 1 } else { -(-4) }; x * x",
+            "if (!true) { 1 }",
+            "let g = fn(a) { a == (1 == 1) }; g(true)",
+        ];
+
+        for input in test_cases {
+            let program = parse(input).unwrap();
+            let expected = eval(&program, &Environment::new()).unwrap();
+            let actual = eval(&optimize(program), &Environment::new()).unwrap();
+
+            assert_eq!(actual.inspect(), expected.inspect(), "{}", input);
+        }
+    }
+}
@@ -2,9 +2,11 @@ extern crate lolo;
 
 #[cfg(test)]
 mod test {
+    use lolo::evaluator::STACK_SIZE;
     use lolo::lexer::Lexer;
     use lolo::parser::ast::{expressions::*, statements::*, *};
-    use lolo::parser::Parser;
+    use lolo::parser::{parse, Parser, MAX_EXPRESSION_DEPTH};
+    use std::thread;
 
     #[test]
     fn is_should_parse_let_statement() {
@@ -33,6 +35,58 @@ mod test {
         }
     }
 
+    #[test]
+    fn is_should_parse_const_statement() {
+        let lexer = Lexer::new(&"const limit = 10; let x = limit;");
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        assert!(parser.errors().is_empty());
+
+        let constness: Vec<(String, bool)> = program
+            .statements
+            .iter()
+            .map(|x| match x {
+                Statements::LetStatement(x) => (x.name.value.clone(), x.is_const()),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            constness,
+            vec![("limit".to_string(), true), ("x".to_string(), false)]
+        );
+        assert_eq!(program.to_string(), "const limit = 10; let x = limit;");
+    }
+
+    #[test]
+    fn is_should_parse_assign_statement() {
+        let test_cases = vec![
+            ("x = 5;", "x", "=", "5"),
+            ("x += y * 2", "x", "+=", "(y * 2)"),
+            ("total -= f(1)", "total", "-=", "f(1)"),
+            ("n *= -1;", "n", "*=", "(-1)"),
+            ("n /= 2 == 1", "n", "/=", "(2 == 1)"),
+        ];
+
+        for (input, name, operator, value) in test_cases {
+            let lexer = Lexer::new(&input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+
+            assert!(parser.errors().is_empty(), "input: {}", input);
+            assert_eq!(program.statements.len(), 1);
+
+            let statement = match program.statements.first().unwrap() {
+                Statements::AssignStatement(x) => x,
+                _ => panic!(),
+            };
+
+            assert_eq!(statement.name.value, name);
+            assert_eq!(statement.operator, operator);
+            assert_eq!(statement.value.to_string(), value);
+        }
+    }
+
     #[test]
     fn is_should_parse_return_statement() {
         let input = "
@@ -89,7 +143,29 @@ mod test {
             _ => panic!(),
         };
 
-        test_integer_literal(&expression, 5)
+        test_integer_literal(expression, 5)
+    }
+
+    #[test]
+    fn is_should_parse_integer_literal_too_large_for_64_bits() {
+        let input = "99999999999999999999;";
+
+        let lexer = Lexer::new(&input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+
+        assert!(parser.errors().is_empty());
+
+        let literal = match program.statements.first().unwrap() {
+            Statements::ExpressionStatement(ExpressionStatement {
+                expression: Expression::BigIntLiteral(x),
+                ..
+            }) => x,
+            _ => panic!(),
+        };
+
+        assert_eq!(literal.value.to_string(), "99999999999999999999");
+        assert_eq!(literal.token.literal, "99999999999999999999");
     }
 
     #[test]
@@ -179,6 +255,25 @@ mod test {
             ("a + (b + c)", "(a + (b + c))"),
             ("(a + b) * c", "((a + b) * c)"),
             ("!(true == false)", "(!(true == false))"),
+            ("a <= b == c >= d", "((a <= b) == (c >= d))"),
+            ("a || b && c", "(a || (b && c))"),
+            ("a && b || c && d", "((a && b) || (c && d))"),
+            ("a == b && c != d", "((a == b) && (c != d))"),
+            ("!a || b + 1 > c", "((!a) || ((b + 1) > c))"),
+            ("a * b % c", "((a * b) % c)"),
+            ("a ** b ** c", "(a ** (b ** c))"),
+            ("-a ** b", "(-(a ** b))"),
+            ("a ** -b", "(a ** (-b))"),
+            ("a * b ** c", "(a * (b ** c))"),
+            ("f(a) ** 2", "(f(a) ** 2)"),
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+            ("a & b == c", "((a & b) == c)"),
+            ("a << b + c", "(a << (b + c))"),
+            ("a | b < c << d", "((a | b) < (c << d))"),
+            ("~a & b", "((~a) & b)"),
+            ("a ?? b || c", "(a ?? (b || c))"),
+            ("a ?? b ?? c", "((a ?? b) ?? c)"),
+            ("null ?? a == null", "(null ?? (a == null))"),
         ];
 
         for expected in prefix_tests {
@@ -254,7 +349,7 @@ mod test {
         };
 
         test_identifier(expression_statement, "x");
-        assert_eq!(expression.alternative.is_none(), true);
+        assert!(expression.alternative.is_none());
     }
 
     #[test]
@@ -278,7 +373,7 @@ mod test {
             _ => panic!(),
         };
 
-        assert_eq!(function.parameters.len(), 2);;
+        assert_eq!(function.parameters.len(), 2);
 
         assert_eq!(function.parameters[0].value, "x");
         assert_eq!(function.parameters[1].value, "y");
@@ -310,6 +405,82 @@ mod test {
         assert_eq!(call.arguments.len(), 4);
     }
 
+    #[test]
+    fn is_should_report_parse_errors() {
+        let test_cases = vec![
+            (
+                "let = 5;",
+                vec![
+                    "expected next token to be IDENT, got ASSIGN instead",
+                    "no prefix parse function for ASSIGN found",
+                ],
+            ),
+            (
+                "let x 5;",
+                vec!["expected next token to be ASSIGN, got INT instead"],
+            ),
+            (
+                "1 + ;",
+                vec!["no prefix parse function for SEMICOLON found"],
+            ),
+            (
+                "add(1, 2",
+                vec!["expected next token to be RPAREN, got EOF instead"],
+            ),
+            ("retrun 5;", vec!["unexpected identifier `retrun`"]),
+            ("lett x = 5;", vec!["unexpected identifier `lett`"]),
+        ];
+
+        for t in test_cases {
+            let lexer = Lexer::new(&t.0);
+            let mut parser = Parser::new(lexer);
+            parser.parse_program();
+
+            let messages: Vec<String> = parser.errors().iter().map(|e| e.to_string()).collect();
+            assert_eq!(messages, t.1, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_reject_expressions_nested_too_deep() {
+        // the parser recurses once per level, so it gets the binary's stack
+        let test = || {
+            let test_cases = vec![
+                vec!["1"; 20_000].join(" + "),
+                format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000)),
+                format!("{}1", "-".repeat(100_000)),
+                format!("let f = fn() {{ {} }};", "if (true) { ".repeat(2_000)),
+            ];
+
+            for input in test_cases {
+                let messages: Vec<String> = match parse(&input) {
+                    Ok(_) => vec![],
+                    Err(errors) => errors.iter().map(|e| e.to_string()).collect(),
+                };
+                assert_eq!(messages, vec!["expression nested too deeply"]);
+            }
+
+            // the deepest expressions allowed
+            let depth = MAX_EXPRESSION_DEPTH - 1;
+            let test_cases = vec![
+                vec!["1"; depth].join(" + "),
+                format!("{}1{}", "(".repeat(depth), ")".repeat(depth)),
+                format!("{}1", "-".repeat(depth)),
+            ];
+
+            for input in test_cases {
+                assert!(parse(&input).is_ok());
+            }
+        };
+
+        thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(test)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
     fn test_let_statement(stmt: &Statements, name: &str) {
         assert_eq!(stmt.token_literal(), "let");
 
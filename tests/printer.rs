@@ -0,0 +1,208 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::parser::ast::printer::{print, print_expression};
+    use lolo::parser::ast::statements::Statements;
+    use lolo::parser::parse;
+
+    #[test]
+    fn is_should_print_programs() {
+        let test_cases = vec![
+            ("let x = 5", "let x = 5;\n"),
+            ("const   x=5", "const x = 5;\n"),
+            ("return x", "return x;\n"),
+            ("x = 5", "x = 5;\n"),
+            ("x += (a + b) * c", "x += (a + b) * c;\n"),
+            (
+                "let f = fn() { n -= 1 }",
+                "let f = fn() {\n    n -= 1;\n};\n",
+            ),
+            ("a + b * c", "a + b * c;\n"),
+            ("(a + b) * c", "(a + b) * c;\n"),
+            ("a - (b - c)", "a - (b - c);\n"),
+            ("(a - b) - c", "a - b - c;\n"),
+            ("-(a + b)", "-(a + b);\n"),
+            ("!-a", "!-a;\n"),
+            ("(-a)(b)", "(-a)(b);\n"),
+            ("add(1, 2 * 3, f(x))", "add(1, 2 * 3, f(x));\n"),
+            ("fn(x) { x }(5)", "fn(x) {\n    x\n}(5);\n"),
+            ("007", "007;\n"),
+            ("fn() {}", "fn() {};\n"),
+            (
+                "let add = fn(x, y) { let z = x + y; z; };",
+                "let add = fn(x, y) {\n    let z = x + y;\n    z\n};\n",
+            ),
+            (
+                "if (x < y) { x } else { if (y) { y } }",
+                "if (x < y) {\n    x\n} else {\n    if (y) {\n        y\n    }\n}\n",
+            ),
+            (
+                "if (a) { 1 }; -1; if (b) { 2 } let c = 3;",
+                "if (a) {\n    1\n};\n-1;\nif (b) {\n    2\n}\nlet c = 3;\n",
+            ),
+        ];
+
+        for (input, expected) in test_cases {
+            let program = parse(input).unwrap();
+
+            assert_eq!(print(&program), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_print_expressions_on_one_line_for_display() {
+        let test_cases = vec![
+            ("add(1, 2)", "add(1, 2)"),
+            ("fn(x, y) { x; y }", "fn(x, y) { x; y }"),
+            ("fn() {}", "fn() {}"),
+            ("if (x) { 1 } else { 2 }", "if (x) { 1 } else { 2 }"),
+            ("if (x < 1) { let y = 2; y }", "if (x < 1) { let y = 2; y }"),
+        ];
+
+        for (input, expected) in test_cases {
+            let program = parse(input).unwrap();
+            let expression = match &program.statements[0] {
+                Statements::ExpressionStatement(x) => &x.expression,
+                _ => panic!(),
+            };
+
+            assert_eq!(expression.to_string(), expected);
+            // the display form is valid source too
+            let reparsed = parse(expected).unwrap();
+            assert_eq!(reparsed, program, "input: {}", input);
+            assert!(!print_expression(expression).is_empty());
+        }
+    }
+
+    #[test]
+    fn is_should_round_trip_generated_programs() {
+        let mut generator = Generator::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..500 {
+            let source = generator.program();
+            let ast = match parse(&source) {
+                Ok(x) => x,
+                Err(e) => panic!("generated invalid source {:?}: {}", source, e[0]),
+            };
+
+            let printed = print(&ast);
+            let reparsed = match parse(&printed) {
+                Ok(x) => x,
+                Err(e) => panic!("printed invalid source {:?}: {}", printed, e[0]),
+            };
+
+            assert_eq!(reparsed, ast, "source: {}\nprinted: {}", source, printed);
+            assert_eq!(print(&reparsed), printed, "printing is not stable");
+        }
+    }
+
+    // writes random programs, sometimes with redundant parentheses and sometimes
+    // without any, so the parser decides the shape of the tree
+    struct Generator {
+        state: u64,
+    }
+
+    impl Generator {
+        fn new(seed: u64) -> Generator {
+            Generator { state: seed }
+        }
+
+        // xorshift64*
+        fn below(&mut self, n: u64) -> u64 {
+            self.state ^= self.state >> 12;
+            self.state ^= self.state << 25;
+            self.state ^= self.state >> 27;
+            self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) % n
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.below(items.len() as u64) as usize]
+        }
+
+        fn program(&mut self) -> String {
+            let count = 1 + self.below(4);
+            (0..count)
+                .map(|_| self.statement(3))
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+
+        fn statement(&mut self, depth: u64) -> String {
+            match self.below(4) {
+                0 => format!("let {} = {};", self.name(), self.expression(depth)),
+                1 => format!("return {};", self.expression(depth)),
+                _ => format!("{};", self.expression(depth)),
+            }
+        }
+
+        fn block(&mut self, depth: u64) -> String {
+            let count = self.below(3);
+            let statements: Vec<String> = (0..count).map(|_| self.statement(depth)).collect();
+            format!("{{ {} }}", statements.join(" "))
+        }
+
+        fn name(&mut self) -> String {
+            self.pick(&["a", "b", "count", "total", "f"]).to_string()
+        }
+
+        fn atom(&mut self) -> String {
+            match self.below(4) {
+                0 => self.name(),
+                1 => self.pick(&["true", "false", "null"]).to_string(),
+                2 => self.pick(&["0", "007"]).to_string(),
+                _ => self.below(1000).to_string(),
+            }
+        }
+
+        fn operand(&mut self, depth: u64) -> String {
+            let expression = self.expression(depth);
+            if self.below(2) == 0 {
+                format!("({})", expression)
+            } else {
+                expression
+            }
+        }
+
+        fn expression(&mut self, depth: u64) -> String {
+            if depth == 0 {
+                return self.atom();
+            }
+
+            let depth = depth - 1;
+            match self.below(9) {
+                0 => self.atom(),
+                1 => format!("{}{}", self.pick(&["-", "!", "~"]), self.operand(depth)),
+                2 | 3 => {
+                    let operator = self.pick(&[
+                        "+", "-", "*", "/", "%", "**", "&", "|", "^", "<<", ">>", "<", ">", "<=",
+                        ">=", "==", "!=", "&&", "||", "??",
+                    ]);
+                    format!(
+                        "{} {} {}",
+                        self.operand(depth),
+                        operator,
+                        self.operand(depth)
+                    )
+                }
+                4 => format!("({})", self.expression(depth)),
+                5 => {
+                    let mut text = format!("if ({}) {}", self.expression(depth), self.block(depth));
+                    if self.below(2) == 0 {
+                        text = format!("{} else {}", text, self.block(depth));
+                    }
+                    text
+                }
+                6 => {
+                    let parameters: Vec<String> = (0..self.below(3)).map(|_| self.name()).collect();
+                    format!("fn({}) {}", parameters.join(", "), self.block(depth))
+                }
+                _ => {
+                    let arguments: Vec<String> =
+                        (0..self.below(3)).map(|_| self.expression(depth)).collect();
+                    format!("{}({})", self.operand(depth), arguments.join(", "))
+                }
+            }
+        }
+    }
+}
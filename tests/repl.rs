@@ -0,0 +1,161 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::lexer::Lexer;
+    use lolo::object::Environment;
+    use lolo::parser::Parser;
+    use lolo::repl::editor::{complete, read_key, Key};
+    use lolo::repl::highlight::{highlight, pretty};
+    use lolo::repl::history::History;
+    use lolo::repl::{is_complete, Command};
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn is_should_detect_complete_input() {
+        let test_cases = vec![
+            ("5", true),
+            ("let x = 5;", true),
+            ("fn(x) { x }", true),
+            ("if (true) { 10 } else { 20 }", true),
+            ("", true),
+            ("fn(x) {", false),
+            ("fn(x) {\n x + 1", false),
+            ("add(1,", false),
+            ("(1 + 2", false),
+            ("1 +", false),
+            ("let x =", false),
+            ("1 ==", false),
+            ("if (1 > 2) { 10 } else {", false),
+        ];
+
+        for t in test_cases {
+            assert_eq!(is_complete(t.0), t.1, "input: {:?}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_parse_meta_commands() {
+        let test_cases = vec![
+            (":tokens 1 + 2", Some(Command::Tokens("1 + 2".to_string()))),
+            (
+                ":ast let x = 5;",
+                Some(Command::Ast("let x = 5;".to_string())),
+            ),
+            (":env", Some(Command::Env)),
+            (":load a.lolo", Some(Command::Load("a.lolo".to_string()))),
+            (":time fib(20)", Some(Command::Time("fib(20)".to_string()))),
+            (":reset", Some(Command::Reset)),
+            (":help", Some(Command::Help)),
+            (":foo bar", Some(Command::Unknown(":foo".to_string()))),
+            ("1 + 2", None),
+        ];
+
+        for t in test_cases {
+            assert_eq!(Command::parse(t.0), t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_decode_keys() {
+        let input = "a\r\t\x7f\x1b[A\x1b[B\x1b[C\x1b[D\x1b[H\x1b[F\x1b[3~\x03\x04\x12あ";
+        let expects = vec![
+            Key::Char('a'),
+            Key::Enter,
+            Key::Tab,
+            Key::Backspace,
+            Key::Up,
+            Key::Down,
+            Key::Right,
+            Key::Left,
+            Key::Home,
+            Key::End,
+            Key::Delete,
+            Key::CtrlC,
+            Key::CtrlD,
+            Key::CtrlR,
+            Key::Char('あ'),
+        ];
+
+        let mut bytes = input.as_bytes();
+        for expect in expects {
+            assert_eq!(read_key(&mut bytes).unwrap(), Some(expect));
+        }
+
+        assert_eq!(read_key(&mut bytes).unwrap(), None);
+    }
+
+    #[test]
+    fn is_should_complete_words() {
+        let candidates: Vec<String> = vec!["let", "fn", "if", "false", "fib", "fib", "total"]
+            .into_iter()
+            .map(|x| x.to_string())
+            .collect();
+
+        assert_eq!(complete("f", &candidates), vec!["false", "fib", "fn"]);
+        assert_eq!(complete("fi", &candidates), vec!["fib"]);
+        assert_eq!(complete("to", &candidates), vec!["total"]);
+        assert!(complete("x", &candidates).is_empty());
+    }
+
+    #[test]
+    fn is_should_record_and_search_history() {
+        let path = env::temp_dir().join(format!("lolo_history_test_{}", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        let mut history = History::load(path.clone());
+        history.push("let x = 5;").unwrap();
+        history.push("let x = 5;").unwrap();
+        history.push("").unwrap();
+        history.push("x + 1").unwrap();
+        history.push("let y = x;").unwrap();
+
+        assert_eq!(history.entries(), &["let x = 5;", "x + 1", "let y = x;"]);
+        assert_eq!(history.search("let", history.len()), Some(2));
+        assert_eq!(history.search("let", 2), Some(0));
+        assert_eq!(history.search("z", history.len()), None);
+
+        let reloaded = History::load(path.clone());
+        assert_eq!(reloaded.entries(), history.entries());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn is_should_highlight_tokens() {
+        assert_eq!(
+            highlight("let x = 5 + y;"),
+            "\x1b[35mlet\x1b[0m x \x1b[36m=\x1b[0m \x1b[33m5\x1b[0m \x1b[36m+\x1b[0m y;"
+        );
+        assert_eq!(highlight("  if  "), "  \x1b[35mif\x1b[0m  ");
+        assert_eq!(highlight("a @ b"), "a \x1b[31m@\x1b[0m b");
+        assert_eq!(highlight(""), "");
+        assert_eq!(highlight("x // let x"), "x \x1b[2m// let x\x1b[0m");
+        assert_eq!(highlight("a\0b"), "a\x1b[31m\0\x1b[0mb");
+    }
+
+    #[test]
+    fn is_should_pretty_print_objects() {
+        let test_cases = vec![
+            ("5", "5", "\x1b[33m5\x1b[0m"),
+            ("1 < 2", "true", "\x1b[35mtrue\x1b[0m"),
+            ("if (false) { 1 }", "null", "\x1b[2mnull\x1b[0m"),
+            (
+                "fn(x, y) { let z = x; z + y }",
+                "fn(x, y) {\n    let z = x;\n    z + y\n}",
+                "\x1b[35mfn\x1b[0m(x, y) {\n    \x1b[35mlet\x1b[0m z \x1b[36m=\x1b[0m x;\n    z \x1b[36m+\x1b[0m y\n}",
+            ),
+        ];
+
+        for t in test_cases {
+            let lexer = Lexer::new(&t.0);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+            let evaluated = lolo::evaluator::eval(&program, &Environment::new()).unwrap();
+
+            assert_eq!(pretty(&evaluated, false), t.1);
+            assert_eq!(pretty(&evaluated, true), t.2);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::lexer::Lexer;
+    use lolo::parser::Parser;
+    use lolo::resolver::resolve;
+
+    #[test]
+    fn is_should_accept_valid_programs() {
+        let test_cases = vec![
+            "let x = 5; x + 1;",
+            "let add = fn(x, y) { x + y }; add(1, 2);",
+            "let fib = fn(n) { if (n < 2) { return n; } fib(n - 1) + fib(n - 2) }; fib(10);",
+            "let even = fn(n) { if (n == 0) { true } else { odd(n - 1) } }; let odd = fn(n) { if (n == 0) { false } else { even(n - 1) } };",
+            "let adder = fn(x) { fn(y) { x + y } }; adder(1)(2);",
+            "if (true) { let y = 1; } y;",
+            "let n = 0; let inc = fn() { n += 1; }; n = 5;",
+            "const limit = 10; let f = fn(limit) { limit = 1; }; let g = fn() { let limit = 2; };",
+            "let x = 1; const x = 2; x + 1;",
+        ];
+
+        for input in test_cases {
+            assert!(resolve_input(input).is_empty(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn is_should_report_problems() {
+        let test_cases = vec![
+            ("x", vec![("E0200", "cannot find `x` in this scope", 1, 1)]),
+            (
+                "let f = fn() { total = 1; };",
+                vec![("E0200", "cannot find `total` in this scope", 1, 16)],
+            ),
+            (
+                "let x = x;",
+                vec![("E0200", "cannot find `x` in this scope", 1, 9)],
+            ),
+            (
+                "let f = fn(a) { a + b };",
+                vec![("E0200", "cannot find `b` in this scope", 1, 21)],
+            ),
+            (
+                "let f = fn(a) { a }; a",
+                vec![("E0200", "cannot find `a` in this scope", 1, 22)],
+            ),
+            (
+                "fn(a, b, a) { a }",
+                vec![(
+                    "E0201",
+                    "parameter `a` is bound more than once in this parameter list",
+                    1,
+                    10,
+                )],
+            ),
+            (
+                "return 1;",
+                vec![("E0202", "return outside of a function", 1, 1)],
+            ),
+            (
+                "let f = fn() { z };\nreturn f();",
+                vec![
+                    ("E0200", "cannot find `z` in this scope", 1, 16),
+                    ("E0202", "return outside of a function", 2, 1),
+                ],
+            ),
+            (
+                "const x = 1; x = 2;",
+                vec![("E0203", "cannot assign to constant `x`", 1, 14)],
+            ),
+            (
+                "const x = 1; let f = fn() { x *= 2; };",
+                vec![("E0203", "cannot assign to constant `x`", 1, 29)],
+            ),
+            (
+                "const x = 1; let x = 2;",
+                vec![(
+                    "E0204",
+                    "cannot redeclare constant `x` in the same scope",
+                    1,
+                    18,
+                )],
+            ),
+            (
+                "const x = 1; if (true) { const x = 2; }",
+                vec![(
+                    "E0204",
+                    "cannot redeclare constant `x` in the same scope",
+                    1,
+                    32,
+                )],
+            ),
+        ];
+
+        for t in test_cases {
+            let diagnostics: Vec<(String, String, usize, usize)> = resolve_input(t.0)
+                .into_iter()
+                .map(|d| {
+                    let span = d.primary.unwrap().span;
+                    (d.code, d.message, span.line, span.column)
+                })
+                .collect();
+
+            let expected: Vec<(String, String, usize, usize)> =
+                t.1.into_iter()
+                    .map(|(code, message, line, column)| {
+                        (code.to_string(), message.to_string(), line, column)
+                    })
+                    .collect();
+
+            assert_eq!(diagnostics, expected, "input: {}", t.0);
+        }
+    }
+
+    #[test]
+    fn is_should_suggest_similar_names() {
+        let diagnostics = resolve_input("let total = 1; totl");
+
+        assert_eq!(diagnostics[0].helps, vec!["did you mean `total`?"]);
+    }
+
+    #[test]
+    fn is_should_point_at_the_declaration_of_a_constant() {
+        let diagnostics = resolve_input("const limit = 10;\nlimit = 20;");
+        let secondary = &diagnostics[0].secondary[0];
+
+        assert_eq!(secondary.message, "declared as constant here");
+        assert_eq!((secondary.span.line, secondary.span.column), (1, 7));
+        assert_eq!(
+            diagnostics[0].helps,
+            vec!["declare it with `let limit = ...` if it needs to change"]
+        );
+    }
+
+    fn resolve_input(input: &str) -> Vec<lolo::diagnostics::Diagnostic> {
+        let lexer = Lexer::new(&input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+
+        resolve(&program)
+    }
+}
@@ -0,0 +1,37 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::utils::{edit_distance, suggest};
+
+    #[test]
+    fn is_should_measure_edit_distance() {
+        let test_cases = vec![
+            ("", "", 0),
+            ("let", "let", 0),
+            ("let", "lett", 1),
+            ("retrun", "return", 2),
+            ("fib", "fob", 1),
+            ("kitten", "sitting", 3),
+            ("", "abc", 3),
+        ];
+
+        for t in test_cases {
+            assert_eq!(edit_distance(t.0, t.1), t.2, "{} {}", t.0, t.1);
+        }
+    }
+
+    #[test]
+    fn is_should_suggest_closest_name() {
+        let candidates = vec!["let", "return", "fib", "total", "if"];
+
+        assert_eq!(suggest("retrun", candidates.clone()), Some("return"));
+        assert_eq!(suggest("fob", candidates.clone()), Some("fib"));
+        assert_eq!(suggest("totl", candidates.clone()), Some("total"));
+        assert_eq!(suggest("x", candidates.clone()), None);
+        assert_eq!(suggest("iff", candidates.clone()), Some("if"));
+        assert_eq!(suggest("completely", candidates.clone()), None);
+        assert_eq!(suggest("cont", vec!["const", "count"]), Some("count"));
+        assert_eq!(suggest("let", candidates), None);
+    }
+}
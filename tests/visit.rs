@@ -0,0 +1,145 @@
+extern crate lolo;
+
+#[cfg(test)]
+mod test {
+    use lolo::lexer::token::{Token, TokenType};
+    use lolo::parser::ast::expressions::*;
+    use lolo::parser::ast::fold::{self, Fold};
+    use lolo::parser::ast::visit::{self, Visitor};
+    use lolo::parser::ast::visit_mut::VisitorMut;
+    use lolo::parser::parse;
+
+    #[derive(Default)]
+    struct Names {
+        uses: Vec<String>,
+        bindings: Vec<String>,
+    }
+
+    impl Visitor for Names {
+        fn visit_identifier(&mut self, identifier: &Identifier) {
+            self.uses.push(identifier.value.clone());
+        }
+
+        fn visit_binding(&mut self, identifier: &Identifier) {
+            self.bindings.push(identifier.value.clone());
+        }
+    }
+
+    #[test]
+    fn is_should_visit_every_name() {
+        let test_cases = vec![
+            ("let x = y;", vec!["y"], vec!["x"]),
+            ("fn(a, b) { a + c }", vec!["a", "c"], vec!["a", "b"]),
+            (
+                "if (a < b) { f(x, -y) } else { return !z; }",
+                vec!["a", "b", "f", "x", "y", "z"],
+                vec![],
+            ),
+            (
+                "let f = fn(n) { f(n) }; f",
+                vec!["f", "n", "f"],
+                vec!["n", "f"],
+            ),
+        ];
+
+        for (input, uses, bindings) in test_cases {
+            let mut names = Names::default();
+            names.visit_program(&parse(input).unwrap());
+
+            assert_eq!(names.uses, uses);
+            assert_eq!(names.bindings, bindings);
+        }
+    }
+
+    // counts calls, but not the ones inside function bodies
+    #[derive(Default)]
+    struct Calls(usize);
+
+    impl Visitor for Calls {
+        fn visit_call_expression(&mut self, call: &CallExpression) {
+            self.0 += 1;
+            visit::walk_call_expression(self, call);
+        }
+
+        fn visit_function_literal(&mut self, _function: &FunctionLiteral) {}
+    }
+
+    #[test]
+    fn is_should_stop_where_a_visitor_does_not_walk() {
+        let mut calls = Calls::default();
+        calls.visit_program(&parse("f(g(1)); let h = fn() { f() }; h()").unwrap());
+
+        assert_eq!(calls.0, 3);
+    }
+
+    struct Rename;
+
+    impl VisitorMut for Rename {
+        fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+            identifier.value = identifier.value.to_uppercase();
+        }
+
+        fn visit_binding_mut(&mut self, identifier: &mut Identifier) {
+            identifier.value = identifier.value.to_uppercase();
+        }
+    }
+
+    #[test]
+    fn is_should_change_nodes_in_place() {
+        let mut program = parse("let add = fn(x, y) { x + y }; add(a, 1)").unwrap();
+        Rename.visit_program_mut(&mut program);
+
+        assert_eq!(
+            program.to_string(),
+            "let ADD = fn(X, Y) { (X + Y) }; ADD(A, 1)"
+        );
+    }
+
+    // replaces `x + 0` with `x`, which changes the kind of the node
+    struct DropZero;
+
+    impl Fold for DropZero {
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            match fold::walk_expression(self, expression) {
+                Expression::InfixExpression(x)
+                    if x.operator == "+" && x.right.to_string() == "0" =>
+                {
+                    *x.left
+                }
+                x => x,
+            }
+        }
+
+        fn fold_integer_literal(&mut self, literal: IntegerLiteral) -> IntegerLiteral {
+            let value = literal.value * 10;
+
+            IntegerLiteral {
+                token: Token::new(TokenType::INT, &value),
+                value,
+            }
+        }
+    }
+
+    #[test]
+    fn is_should_rebuild_the_tree() {
+        let test_cases = vec![
+            ("a + 0", "a"),
+            ("a + 0 + 0", "a"),
+            ("f(a + 0, 2)", "f(a, 20)"),
+            (
+                "if (a + 0) { 1 } else { b + 0 }",
+                "if (a) { 10 } else { b }",
+            ),
+            (
+                "let x = fn(y) { return y + 0; };",
+                "let x = fn(y) { return y; };",
+            ),
+        ];
+
+        for (input, expected) in test_cases {
+            let program = DropZero.fold_program(parse(input).unwrap());
+
+            assert_eq!(program, parse(expected).unwrap());
+        }
+    }
+}